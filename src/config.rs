@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -6,6 +7,20 @@ use std::path::Path;
 pub struct Config {
     pub bar: BarConfig,
     pub modules: ModulesConfig,
+    #[serde(default)]
+    pub osd: OsdConfig,
+    #[serde(default)]
+    pub mpris: MprisConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub battery: BatteryConfig,
+    #[serde(default)]
+    pub clock: ClockConfig,
+    #[serde(default)]
+    pub timer: TimerConfig,
+    #[serde(default)]
+    pub workspaces: WorkspacesConfig,
 }
 
 #[derive(Deserialize, Clone)]
@@ -21,6 +36,237 @@ pub struct ModulesConfig {
     pub right: Option<Vec<String>>,
 }
 
+// Pengaturan OSD (popup volume/brightness di tengah layar)
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct OsdConfig {
+    pub enabled: bool,
+    pub timeout_ms: u64,  // Lama OSD tampil sebelum hilang
+    pub backlight: bool,  // Pantau perubahan brightness layar
+}
+
+impl Default for OsdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout_ms: 1500,
+            backlight: true,
+        }
+    }
+}
+
+// Aturan pemilihan player MPRIS (dicocokkan dengan awalan nama bus, misal "spotify")
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct MprisConfig {
+    pub priority: Vec<String>,              // Urutan prioritas, paling depan paling diutamakan
+    pub ignore: Vec<String>,                // Tidak pernah ditampilkan
+    pub ignore_unless_playing: Vec<String>, // Hanya ditampilkan saat sedang Playing
+    pub show_player: bool,                  // Tampilkan nama player yang sedang dikontrol
+    pub max_length: usize,                  // Panjang maksimal "Artis - Judul" (grapheme)
+    pub marquee: bool,                      // Teks berjalan, bukan dipotong "..."
+    pub marquee_interval_ms: u64,
+    pub show_time: bool,     // "1:23/3:45" di samping judul
+    pub show_progress: bool, // Progress bar tipis di bawah judul
+}
+
+impl Default for MprisConfig {
+    fn default() -> Self {
+        Self {
+            priority: Vec::new(),
+            ignore: Vec::new(),
+            ignore_unless_playing: Vec::new(),
+            show_player: true,
+            max_length: 40,
+            marquee: false,
+            marquee_interval_ms: 300,
+            show_time: false,
+            show_progress: false,
+        }
+    }
+}
+
+// Pengaturan widget network
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub throughput: bool,                     // Tampilkan kecepatan upload/download
+    pub throughput_interface: Option<String>, // Kosong = gabungan semua interface fisik
+    pub throughput_interval_secs: u64,
+    pub include_interfaces: Vec<String>,      // Kosong = semua interface (wildcard '*')
+    pub exclude_interfaces: Vec<String>,      // Interface yang diabaikan
+    pub connectivity_url: Option<String>,     // Cek internet lokal; kosong = pakai NetworkManager
+    pub connectivity_response: Option<String>, // Isi balasan yang diharapkan dari URL cek
+    pub connectivity_interval_secs: u64,
+    pub portal_url: String,                   // Dibuka saat klik di status captive portal
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            throughput: false,
+            throughput_interface: None,
+            throughput_interval_secs: 2,
+            include_interfaces: Vec::new(),
+            exclude_interfaces: ["lo", "docker*", "veth*", "virbr*", "br-*"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            connectivity_url: None,
+            connectivity_response: None,
+            connectivity_interval_secs: 60,
+            portal_url: "http://neverssl.com".to_string(),
+        }
+    }
+}
+
+// Widget baterai
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct BatteryConfig {
+    pub peripherals: bool, // Tampilkan baterai mouse/keyboard/headset (scope=Device)
+    pub format: String,    // Placeholder: {icon} {percent} {time} {power}
+    pub low_threshold: i32,      // Persen: kuning + notifikasi
+    pub critical_threshold: i32, // Persen: merah berkedip + notifikasi kritis
+    pub notify: bool,
+    pub critical_action: Option<String>, // "suspend" / "hibernate" / "hybrid-sleep" / "poweroff"
+    pub action_threshold: i32,           // Persen saat aksi kritis mulai dihitung mundur
+    pub action_grace_secs: u64,          // Waktu untuk mencolok charger sebelum aksi jalan
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        Self {
+            peripherals: false,
+            format: "{icon} {percent}%".to_string(),
+            low_threshold: 30,
+            critical_threshold: 15,
+            notify: true,
+            critical_action: None,
+            action_threshold: 5,
+            action_grace_secs: 60,
+        }
+    }
+}
+
+// Widget jam. Format memakai strftime (chrono), misal "%H:%M" atau "%a %d %b"
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct ClockConfig {
+    pub format: String,
+    pub format_alt: Option<String>, // Format kedua, ganti dengan klik kanan
+    pub tooltip_format: String,
+    pub timezone: Option<String>,   // Nama IANA, misal "Asia/Tokyo" (kosong = zona lokal)
+    pub week_numbers: bool,         // Nomor minggu di kalender popover
+    pub calendars: Vec<String>,     // File .ics atau folder vdir (vdirsyncer) untuk agenda
+    pub next_event: bool,           // Tampilkan "Rapat dalam 10m" di bar
+    pub next_event_mins: i64,       // Seberapa jauh ke depan acara berikutnya ditampilkan
+    // Modul "clock#nama" memakai [clock.instances.nama], field kosong ikut [clock]
+    pub instances: HashMap<String, ClockInstance>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClockInstance {
+    pub format: Option<String>,
+    pub format_alt: Option<String>,
+    pub tooltip_format: Option<String>,
+    pub timezone: Option<String>,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            format: "%H:%M:%S".to_string(),
+            format_alt: None,
+            tooltip_format: "%A, %d %B %Y".to_string(),
+            timezone: None,
+            week_numbers: true,
+            calendars: Vec::new(),
+            next_event: false,
+            next_event_mins: 30,
+            instances: HashMap::new(),
+        }
+    }
+}
+
+impl ClockConfig {
+    // Config untuk satu instance, None kalau nama instance tidak ada di config
+    pub fn instance(&self, name: &str) -> Option<ClockConfig> {
+        let instance = self.instances.get(name)?;
+        let mut config = self.clone();
+        if let Some(format) = &instance.format {
+            config.format = format.clone();
+        }
+        if instance.format_alt.is_some() {
+            config.format_alt = instance.format_alt.clone();
+        }
+        if let Some(format) = &instance.tooltip_format {
+            config.tooltip_format = format.clone();
+        }
+        if instance.timezone.is_some() {
+            config.timezone = instance.timezone.clone();
+        }
+        Some(config)
+    }
+}
+
+// Widget workspace Hyprland
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct WorkspacesConfig {
+    pub persistent: Vec<i32>,                          // Selalu tampil walau belum ada, misal 1..10
+    pub persistent_monitors: HashMap<String, Vec<i32>>, // Workspace persisten per monitor (butuh `monitor`)
+    pub monitor: Option<String>,                       // Hanya workspace di monitor ini
+    pub show_special: bool,                            // Tampilkan special workspace (scratchpad)
+    pub use_names: bool,                               // Pakai nama workspace Hyprland sebagai label
+    pub labels: HashMap<String, String>,               // Label/ikon per id, misal "1" = ""
+}
+
+impl Default for WorkspacesConfig {
+    fn default() -> Self {
+        Self {
+            persistent: Vec::new(),
+            persistent_monitors: HashMap::new(),
+            monitor: None,
+            show_special: false,
+            use_names: true,
+            labels: HashMap::new(),
+        }
+    }
+}
+
+// Widget timer: hitung mundur, stopwatch, atau pomodoro
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct TimerConfig {
+    pub mode: String,           // "countdown" / "stopwatch" / "pomodoro" (awal, bisa diganti klik tengah)
+    pub duration_mins: i64,     // Durasi awal hitung mundur
+    pub step_mins: i64,         // Perubahan durasi per scroll
+    pub work_mins: i64,         // Pomodoro: sesi fokus
+    pub short_break_mins: i64,
+    pub long_break_mins: i64,
+    pub long_break_every: u32,  // Istirahat panjang setiap N sesi fokus
+    pub notify: bool,
+    pub persist: bool,          // Simpan state di $XDG_STATE_HOME/finshell/timer.json (timer#2 -> timer-2.json)
+}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            mode: "countdown".to_string(),
+            duration_mins: 10,
+            step_mins: 1,
+            work_mins: 25,
+            short_break_mins: 5,
+            long_break_mins: 15,
+            long_break_every: 4,
+            notify: true,
+            persist: true,
+        }
+    }
+}
+
 impl Config {
     // Fungsi untuk memuat config
     pub fn load() -> Self {
@@ -63,6 +309,13 @@ impl Config {
                 center: Some(vec!["clock".to_string()]),
                 right: Some(vec!["battery".to_string()]),
             },
+            osd: OsdConfig::default(),
+            mpris: MprisConfig::default(),
+            network: NetworkConfig::default(),
+            battery: BatteryConfig::default(),
+            clock: ClockConfig::default(),
+            timer: TimerConfig::default(),
+            workspaces: WorkspacesConfig::default(),
         }
     }
 }
\ No newline at end of file
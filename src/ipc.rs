@@ -0,0 +1,190 @@
+// Socket IPC di $XDG_RUNTIME_DIR/finshell.sock (tanpa XDG_RUNTIME_DIR:
+// /tmp/finshell-<uid>/finshell.sock). Satu baris perintah per koneksi,
+// kata pertama = nama modul, misal "timer toggle". Dari terminal/keybind:
+//   finshell msg timer toggle
+// Socket dibaca di worker thread, perintahnya dijalankan di GTK thread lewat
+// handler yang didaftarkan modul (register), lalu balasannya dikirim balik.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+type Handler = Box<dyn Fn(&[&str]) -> Result<String, String>>;
+
+struct Request {
+    line: String,
+    reply: mpsc::Sender<Result<String, String>>,
+}
+
+// Handler hanya dipanggil dari GTK thread, jadi cukup thread_local
+thread_local! {
+    static HANDLERS: RefCell<HashMap<String, Handler>> = RefCell::new(HashMap::new());
+}
+
+// /tmp bisa ditulis semua user: socket langsung di sana bisa dibajak atau
+// diganti orang lain. Fallback-nya direktori per uid yang hanya boleh milik kita
+// dengan izin 0700; kalau tidak memenuhi, IPC ditolak. `create` hanya untuk server.
+fn socket_path(create: bool) -> Result<PathBuf, String> {
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(dir).join("finshell.sock"));
+    }
+
+    let uid = unsafe { libc::getuid() };
+    let dir = PathBuf::from(format!("/tmp/finshell-{}", uid));
+    if create {
+        match fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(format!("Gagal membuat {}: {}", dir.display(), e)),
+        }
+    }
+    // symlink_metadata: symlink buatan user lain tidak diikuti
+    let meta = fs::symlink_metadata(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    if !meta.is_dir() || meta.uid() != uid || meta.mode() & 0o077 != 0 {
+        return Err(format!("{} bukan direktori pribadi (harus milik uid {} dengan izin 0700)", dir.display(), uid));
+    }
+    Ok(dir.join("finshell.sock"))
+}
+
+// Daftarkan perintah untuk satu modul. Handler menerima argumen setelah nama modul.
+// Modul yang sama bisa dipasang lebih dari sekali: instance berikutnya tidak
+// menimpa yang pertama tapi dapat nama bernomor ("timer#2", "timer#3", ...).
+// Return nama yang akhirnya dipakai.
+pub fn register(target: &str, handler: impl Fn(&[&str]) -> Result<String, String> + 'static) -> String {
+    let id = available_id(target);
+    HANDLERS.with(|h| h.borrow_mut().insert(id.clone(), Box::new(handler)));
+    id
+}
+
+// Nama yang akan didapat register(target), untuk modul yang butuh nama itu
+// sebelum handler-nya siap (misal nama file state)
+pub fn available_id(target: &str) -> String {
+    HANDLERS.with(|h| {
+        let handlers = h.borrow();
+        let mut id = target.to_string();
+        let mut n = 1;
+        while handlers.contains_key(&id) {
+            n += 1;
+            id = format!("{}#{}", target, n);
+        }
+        id
+    })
+}
+
+fn dispatch(line: &str) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (target, args) = words.split_first().ok_or("Perintah kosong")?;
+    HANDLERS.with(|h| match h.borrow().get(*target) {
+        Some(handler) => handler(args),
+        None => Err(format!("Modul '{}' tidak dikenal atau tidak aktif", target)),
+    })
+}
+
+fn bind() -> Option<UnixListener> {
+    let path = match socket_path(true) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("[IPC] {}, IPC dimatikan", e);
+            return None;
+        }
+    };
+    match UnixListener::bind(&path) {
+        Ok(listener) => Some(listener),
+        Err(e) if e.kind() == ErrorKind::AddrInUse => {
+            // Masih ada finshell lain yang hidup? Kalau tidak, file sisa crash
+            if UnixStream::connect(&path).is_ok() {
+                eprintln!("[IPC] {} sudah dipakai instance lain", path.display());
+                return None;
+            }
+            let _ = fs::remove_file(&path);
+            UnixListener::bind(&path).map_err(|e| eprintln!("[IPC] Gagal membuat socket: {}", e)).ok()
+        }
+        Err(e) => {
+            eprintln!("[IPC] Gagal membuat socket: {}", e);
+            None
+        }
+    }
+}
+
+fn serve(mut stream: UnixStream, sender: &mpsc::Sender<Request>) {
+    let _ = stream.set_read_timeout(Some(REPLY_TIMEOUT));
+    let mut line = String::new();
+    if BufReader::new(&stream).read_line(&mut line).is_err() {
+        return;
+    }
+
+    let (reply_sender, reply_receiver) = mpsc::channel();
+    let _ = sender.send(Request { line: line.trim().to_string(), reply: reply_sender });
+    let response = match reply_receiver.recv_timeout(REPLY_TIMEOUT) {
+        Ok(Ok(text)) => format!("ok {}", text),
+        Ok(Err(text)) => format!("error {}", text),
+        Err(_) => "error Tidak ada balasan".to_string(),
+    };
+    let _ = writeln!(stream, "{}", response.trim_end());
+}
+
+// Dipanggil sekali dari GTK thread saat aplikasi aktif
+pub fn init() {
+    let listener = match bind() {
+        Some(l) => l,
+        None => return,
+    };
+
+    let (sender, receiver) = mpsc::channel::<Request>();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            serve(stream, &sender);
+        }
+    });
+
+    glib::timeout_add_local(Duration::from_millis(100), move || {
+        for request in receiver.try_iter() {
+            let _ = request.reply.send(dispatch(&request.line));
+        }
+        glib::ControlFlow::Continue
+    });
+}
+
+// Sisi klien (`finshell msg ...`): kirim perintah, cetak balasan, return exit code
+pub fn send(args: &[String]) -> i32 {
+    let path = match socket_path(false) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Finshell tidak berjalan: {}", e);
+            return 1;
+        }
+    };
+    let mut stream = match UnixStream::connect(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Finshell tidak berjalan ({}): {}", path.display(), e);
+            return 1;
+        }
+    };
+    if writeln!(stream, "{}", args.join(" ")).is_err() {
+        return 1;
+    }
+
+    let mut response = String::new();
+    let _ = BufReader::new(&stream).read_line(&mut response);
+    match response.trim().split_once(' ').unwrap_or((response.trim(), "")) {
+        ("ok", text) => {
+            if !text.is_empty() {
+                println!("{}", text);
+            }
+            0
+        }
+        (_, text) => {
+            eprintln!("{}", text);
+            1
+        }
+    }
+}
@@ -1,5 +1,10 @@
 mod modules;
 mod config; // <-- Panggil file config baru
+mod ipc;
+mod notify;
+mod osd;
+mod popover;
+mod text;
 
 use gtk4::prelude::*;
 use gtk4::{
@@ -18,19 +23,32 @@ use modules::mpris::MprisModule;
 use modules::network::NetworkModule;
 use modules::sys_info::SysInfoModule;
 use modules::power::PowerModule;
+use modules::timer::TimerModule;
 
 // --- PABRIK MODUL (Module Factory) ---
 // Fungsi ini menerjemahkan string "clock" menjadi Widget Clock
-fn create_module(name: &str) -> Option<gtk4::Widget> {
+fn create_module(name: &str, config: &Config) -> Option<gtk4::Widget> {
     match name {
-        "workspaces" => Some(WorkspacesModule.build_widget()),
-        "clock" => Some(ClockModule.build_widget()),
-        "battery" => Some(BatteryModule.build_widget()),
+        "workspaces" => Some(WorkspacesModule::new(config.workspaces.clone()).build_widget()),
+        "clock" => Some(ClockModule::new(config.clock.clone()).build_widget()),
+        // Instance jam tambahan, misal "clock#tokyo" -> [clock.instances.tokyo]
+        name if name.starts_with("clock#") => {
+            let instance = name.trim_start_matches("clock#");
+            match config.clock.instance(instance) {
+                Some(clock) => Some(ClockModule::new(clock).build_widget()),
+                None => {
+                    eprintln!("Warning: [clock.instances.{}] tidak ada di config", instance);
+                    None
+                }
+            }
+        }
+        "battery" => Some(BatteryModule::new(config.battery.clone()).build_widget()),
         "audio" => Some(AudioModule.build_widget()),
-        "mpris" => Some(MprisModule.build_widget()),
-        "network" => Some(NetworkModule.build_widget()),
+        "mpris" => Some(MprisModule::new(config.mpris.clone()).build_widget()),
+        "network" => Some(NetworkModule::new(config.network.clone()).build_widget()),
         "sys_info" => Some(SysInfoModule.build_widget()),
         "power" => Some(PowerModule.build_widget()),
+        "timer" => Some(TimerModule::new(config.timer.clone()).build_widget()),
         "spacer" => {
             // Widget kosong untuk peregang jarak (opsional)
             let spacer = Box::new(Orientation::Horizontal, 0);
@@ -45,10 +63,10 @@ fn create_module(name: &str) -> Option<gtk4::Widget> {
 }
 
 // Fungsi helper untuk mengisi kotak (kiri/tengah/kanan) berdasarkan config
-fn fill_box(container: &Box, module_names: &Option<Vec<String>>) {
+fn fill_box(container: &Box, module_names: &Option<Vec<String>>, config: &Config) {
     if let Some(names) = module_names {
         for name in names {
-            if let Some(widget) = create_module(name) {
+            if let Some(widget) = create_module(name, config) {
                 container.append(&widget);
             }
         }
@@ -67,6 +85,12 @@ fn load_css() {
 }
 
 fn main() {
+    // `finshell msg timer toggle` -> kirim ke instance yang sedang berjalan
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("msg") {
+        std::process::exit(ipc::send(&args[2..]));
+    }
+
     let app = Application::builder()
         .application_id("com.arifinn7.finshell")
         .build();
@@ -77,6 +101,12 @@ fn main() {
         
         load_css();
 
+        // OSD volume/brightness (surface overlay terpisah dari bar)
+        osd::init(app, &config.osd);
+
+        // Socket perintah dari luar (finshell msg ...)
+        ipc::init();
+
         let window = ApplicationWindow::builder()
             .application(app)
             .title("Finshell")
@@ -110,17 +140,17 @@ fn main() {
         
         // Kiri
         let left_box = Box::new(Orientation::Horizontal, 5);
-        fill_box(&left_box, &config.modules.left);
+        fill_box(&left_box, &config.modules.left, &config);
         center_box.set_start_widget(Some(&left_box));
 
         // Tengah
         let mid_box = Box::new(Orientation::Horizontal, 5);
-        fill_box(&mid_box, &config.modules.center);
+        fill_box(&mid_box, &config.modules.center, &config);
         center_box.set_center_widget(Some(&mid_box));
 
         // Kanan
         let right_box = Box::new(Orientation::Horizontal, 5);
-        fill_box(&right_box, &config.modules.right);
+        fill_box(&right_box, &config.modules.right, &config);
         center_box.set_end_widget(Some(&right_box));
 
         window.set_child(Some(&center_box));
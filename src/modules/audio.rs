@@ -1,7 +1,17 @@
 use gtk4::prelude::*;
 use gtk4::{Box, Label, Orientation, GestureClick};
 use super::WidgetModule;
-use std::process::Command;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::sync::mpsc;
+use std::time::Duration;
+use crate::osd;
+
+// Polling cadangan kalau `pactl` tidak ada: makin jarang selama volume tidak berubah
+const POLL_MIN: Duration = Duration::from_millis(500);
+const POLL_MAX: Duration = Duration::from_secs(5);
+const RESTART_DELAY: Duration = Duration::from_secs(2);
 
 pub struct AudioModule;
 
@@ -24,9 +34,13 @@ impl AudioModule {
         (0, false)
     }
 
-    fn update_view(label: &Label, container: &Box) {
-        let (volume, is_muted) = Self::get_volume_info();
-        let icon = if is_muted { "󰝟" } else if volume >= 50 { "" } else if volume >= 20 { "" } else { "" };
+    // Dipakai juga oleh OSD supaya ikonnya konsisten dengan bar
+    pub(crate) fn get_icon(volume: i32, is_muted: bool) -> &'static str {
+        if is_muted { "󰝟" } else if volume >= 50 { "" } else if volume >= 20 { "" } else { "" }
+    }
+
+    fn update_view(label: &Label, container: &Box, volume: i32, is_muted: bool) {
+        let icon = Self::get_icon(volume, is_muted);
         
         label.set_text(&format!("{} {}%", icon, volume));
         
@@ -42,6 +56,54 @@ impl AudioModule {
             .spawn();
     }
     
+    // Worker: baca volume hanya saat PipeWire (lewat pipewire-pulse) melapor ada
+    // perubahan sink, jadi bar yang diam tidak spawn wpctl terus-menerus.
+    fn watch_volume(sender: mpsc::Sender<(i32, bool)>) {
+        loop {
+            if sender.send(Self::get_volume_info()).is_err() {
+                return;
+            }
+            let mut child = match Command::new("pactl").arg("subscribe").stdout(Stdio::piped()).spawn() {
+                Ok(c) => c,
+                Err(_) => {
+                    eprintln!("[Audio] pactl tidak tersedia, pakai polling");
+                    Self::poll_volume(sender);
+                    return;
+                }
+            };
+            if let Some(stdout) = child.stdout.take() {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    // "Event 'change' on sink #56", atau "... on server" saat sink default ganti
+                    if !line.contains("on sink #") && !line.contains("on server") {
+                        continue;
+                    }
+                    if sender.send(Self::get_volume_info()).is_err() {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return;
+                    }
+                }
+            }
+            // pactl berhenti (PipeWire restart), sambung ulang
+            let _ = child.wait();
+            thread::sleep(RESTART_DELAY);
+        }
+    }
+
+    fn poll_volume(sender: mpsc::Sender<(i32, bool)>) {
+        let mut last = None;
+        let mut delay = POLL_MIN;
+        loop {
+            let info = Self::get_volume_info();
+            delay = if last == Some(info) { (delay * 2).min(POLL_MAX) } else { POLL_MIN };
+            last = Some(info);
+            if sender.send(info).is_err() {
+                return;
+            }
+            thread::sleep(delay);
+        }
+    }
+
     fn toggle_mute() {
         let _ = Command::new("wpctl").args(["set-mute", "@DEFAULT_AUDIO_SINK@", "toggle"]).spawn();
     }
@@ -57,46 +119,42 @@ impl WidgetModule for AudioModule {
 
         // --- SCROLL ---
         let scroll = gtk4::EventControllerScroll::new(gtk4::EventControllerScrollFlags::VERTICAL);
-        let label_clone_scroll = label.clone();
-        let container_clone_scroll = container.clone();
-        
         scroll.connect_scroll(move |_, _, dy| {
             if dy > 0.0 { Self::run_wpctl(&["5%-"]); } 
             else { Self::run_wpctl(&["5%+", "--limit", "1.0"]); }
-            Self::update_view(&label_clone_scroll, &container_clone_scroll);
             gtk4::glib::Propagation::Stop
         });
         container.add_controller(scroll);
 
         // --- CLICK ---
         let click = GestureClick::new();
-        // Variabel ini "dimiliki" oleh closure 'connect_pressed'
-        let label_for_click = label.clone();
-        let container_for_click = container.clone();
-        
         click.connect_pressed(move |_, _, _, _| {
             Self::toggle_mute();
-            
-            // PERBAIKAN DI SINI:
-            // Kita clone lagi variabelnya UNTUK dipindahkan ke timeout
-            let l = label_for_click.clone();
-            let c = container_for_click.clone();
-            
-            glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-                Self::update_view(&l, &c);
-                glib::ControlFlow::Break
-            });
         });
         container.add_controller(click);
 
-        // --- TIMER ---
-        let label_clone = label.clone();
-        let container_clone = container.clone();
-        
-        Self::update_view(&label, &container);
+        // --- THREADING LOGIC ---
+        // wpctl dijalankan di worker thread supaya UI tidak ikut menunggu proses
+        let (sender, receiver) = mpsc::channel();
+        let container_weak = container.downgrade();
 
-        glib::timeout_add_seconds_local(2, move || {
-            Self::update_view(&label_clone, &container_clone);
+        thread::spawn(move || Self::watch_volume(sender));
+
+        // Nilai terakhir, untuk mendeteksi perubahan (trigger OSD)
+        let mut last_info: Option<(i32, bool)> = None;
+
+        glib::timeout_add_local(Duration::from_millis(100), move || {
+            if let Some(container) = container_weak.upgrade() {
+                if let Some((volume, is_muted)) = receiver.try_iter().last() {
+                    Self::update_view(&label, &container, volume, is_muted);
+
+                    // Render pertama saat startup tidak perlu memunculkan OSD
+                    if last_info.is_some() && last_info != Some((volume, is_muted)) {
+                        osd::show_volume(volume, is_muted);
+                    }
+                    last_info = Some((volume, is_muted));
+                }
+            }
             glib::ControlFlow::Continue
         });
 
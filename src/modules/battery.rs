@@ -1,48 +1,195 @@
 use gtk4::prelude::*;
-use gtk4::{Box, Label, Orientation};
+use gtk4::{Box, GestureClick, Label, Orientation};
 use super::WidgetModule;
-use std::fs;
-use std::path::Path;
+use crate::config::BatteryConfig;
+use crate::popover;
+use dbus::blocking::Connection;
+use alerts::Alerts;
+use controls::BatteryControls;
+use estimate::Estimator;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
-pub struct BatteryModule;
+mod alerts;
+mod controls;
+mod estimate;
+mod profiles;
+mod sysfs;
+mod upower;
+
+// Baterai perangkat (mouse, keyboard, headset, controller)
+#[derive(Clone)]
+struct Peripheral {
+    icon: &'static str,
+    name: String,
+    percentage: i32,
+}
+
+// Status pengisian, dari sysfs `status` atau properti State UPower
+#[derive(Clone, Copy, PartialEq)]
+enum BatteryState {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging, // Charger terpasang tapi tidak mengisi (batas pengisian tercapai)
+    Unknown,
+}
+
+impl BatteryState {
+    // Nilai file sysfs power_supply/*/status
+    fn from_sysfs(status: &str) -> Self {
+        match status {
+            "Charging" => BatteryState::Charging,
+            "Discharging" => BatteryState::Discharging,
+            "Full" => BatteryState::Full,
+            "Not charging" => BatteryState::NotCharging,
+            _ => BatteryState::Unknown,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            BatteryState::Charging => "Mengisi",
+            BatteryState::Discharging => "Memakai baterai",
+            BatteryState::Full => "Penuh",
+            BatteryState::NotCharging => "Terpasang, tidak mengisi",
+            BatteryState::Unknown => "Tidak diketahui",
+        }
+    }
+
+    fn css_class(self) -> Option<&'static str> {
+        match self {
+            BatteryState::Charging => Some("charging"),
+            BatteryState::Discharging => Some("discharging"),
+            BatteryState::Full => Some("full"),
+            BatteryState::NotCharging => Some("not-charging"),
+            BatteryState::Unknown => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct BatteryInfo {
+    present: bool,
+    percentage: i32,
+    state: BatteryState,
+    ac_online: bool,     // Adaptor AC terpasang
+    time_to_empty: i64,  // Detik, 0 = belum diketahui
+    time_to_full: i64,
+    energy_rate: f64,    // Watt
+    energy_now: f64,     // Wh, 0 = backend tidak melaporkan
+    energy_full: f64,
+    health: Option<f64>, // % kapasitas penuh sekarang vs desain
+    cycles: Option<i32>, // Siklus charge (baterai paling tua kalau lebih dari satu)
+    profile: Option<String>, // Power profile aktif (power-profiles-daemon)
+    batteries: Vec<(String, i32)>, // Tiap baterai sistem (nama, persen)
+    peripherals: Vec<Peripheral>,
+}
+
+impl BatteryInfo {
+    // Desktop / tanpa baterai
+    fn absent() -> Self {
+        BatteryInfo {
+            present: false,
+            percentage: -1,
+            state: BatteryState::Unknown,
+            ac_online: true, // Desktop: selalu dari listrik
+            time_to_empty: 0,
+            time_to_full: 0,
+            energy_rate: 0.0,
+            energy_now: 0.0,
+            energy_full: 0.0,
+            health: None,
+            cycles: None,
+            profile: None,
+            batteries: Vec::new(),
+            peripherals: Vec::new(),
+        }
+    }
+}
+
+pub struct BatteryModule {
+    config: BatteryConfig,
+}
 
 impl BatteryModule {
-    // Fungsi untuk membaca persentase (0-100)
-    fn get_percentage() -> i32 {
-        // Coba baca BAT0 (umumnya ini), kalau gagal coba BAT1
-        let paths = ["/sys/class/power_supply/BAT0/capacity", "/sys/class/power_supply/BAT1/capacity"];
-        
-        for path in paths {
-            if Path::new(path).exists() {
-                if let Ok(content) = fs::read_to_string(path) {
-                    // Hapus spasi/enter, lalu ubah ke angka
-                    return content.trim().parse().unwrap_or(0);
-                }
-            }
+    pub fn new(config: BatteryConfig) -> Self {
+        Self { config }
+    }
+
+    // 5400 -> "1j 30m"
+    fn format_duration(secs: i64) -> String {
+        let minutes = secs / 60;
+        if minutes >= 60 {
+            format!("{}j {}m", minutes / 60, minutes % 60)
+        } else {
+            format!("{}m", minutes)
         }
-        -1 // Kode bahwa tidak ada baterai (Desktop PC)
     }
 
-    // Fungsi untuk cek status charging
-    fn get_status() -> String {
-        let paths = ["/sys/class/power_supply/BAT0/status", "/sys/class/power_supply/BAT1/status"];
-        
-        for path in paths {
-            if Path::new(path).exists() {
-                if let Ok(content) = fs::read_to_string(path) {
-                    return content.trim().to_string();
-                }
+    // Sisa waktu sesuai arah arus ("" kalau belum diketahui)
+    fn time_text(info: &BatteryInfo) -> String {
+        match info.state {
+            BatteryState::Charging if info.time_to_full > 0 => Self::format_duration(info.time_to_full),
+            BatteryState::Discharging if info.time_to_empty > 0 => Self::format_duration(info.time_to_empty),
+            _ => String::new(),
+        }
+    }
+
+    // Isi placeholder format label: {icon} {percent} {time} {power}
+    fn render_format(format: &str, icon: &str, info: &BatteryInfo) -> String {
+        let power = if info.energy_rate > 0.0 {
+            format!("{:.1}W", info.energy_rate)
+        } else {
+            String::new()
+        };
+
+        format
+            .replace("{icon}", icon)
+            .replace("{percent}", &info.percentage.to_string())
+            .replace("{time}", &Self::time_text(info))
+            .replace("{power}", &power)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ") // Placeholder kosong jangan meninggalkan spasi ganda
+    }
+
+    fn tooltip_text(info: &BatteryInfo) -> String {
+        let mut lines = vec![format!("Status: {}", info.state.label())];
+        lines.push(format!("AC: {}", if info.ac_online { "terpasang" } else { "tidak terpasang" }));
+        // Laptop dua baterai (ThinkPad): tampilkan masing-masing
+        if info.batteries.len() > 1 {
+            for (name, percentage) in &info.batteries {
+                lines.push(format!("{}: {}%", name, percentage));
             }
         }
-        "Unknown".to_string()
+        if info.state == BatteryState::Charging && info.time_to_full > 0 {
+            lines.push(format!("Penuh dalam {}", Self::format_duration(info.time_to_full)));
+        } else if info.state == BatteryState::Discharging && info.time_to_empty > 0 {
+            lines.push(format!("Sisa {}", Self::format_duration(info.time_to_empty)));
+        }
+        if info.energy_rate > 0.0 {
+            lines.push(format!("Daya: {:.1} W", info.energy_rate));
+        }
+        if let Some(health) = info.health {
+            lines.push(format!("Kesehatan: {:.0}%", health));
+        }
+        if let Some(cycles) = info.cycles {
+            lines.push(format!("Siklus: {}", cycles));
+        }
+        lines.join("\n")
     }
 
     // Menentukan Ikon berdasarkan persen & status
-    fn get_icon(percentage: i32, status: &str) -> &'static str {
-        if status == "Charging" {
-            return "⚡";
+    fn get_icon(percentage: i32, state: BatteryState) -> &'static str {
+        match state {
+            BatteryState::Charging => return "⚡",
+            BatteryState::Full => return "󰁹",
+            BatteryState::NotCharging => return "󰚥", // Colokan: ditahan di batas pengisian
+            _ => {}
         }
-        
+
         match percentage {
             90..=100 => "", // Ikon baterai penuh (Nerd Font)
             60..=89  => "",
@@ -53,33 +200,61 @@ impl BatteryModule {
         }
     }
 
-    fn update_view(label: &Label, container: &Box) {
-        let percentage = Self::get_percentage();
-        
+    fn update_peripherals(peripheral_box: &Box, peripherals: &[Peripheral]) {
+        while let Some(child) = peripheral_box.first_child() {
+            peripheral_box.remove(&child);
+        }
+
+        for peripheral in peripherals {
+            let item = Label::new(Some(&format!("{} {}%", peripheral.icon, peripheral.percentage)));
+            item.add_css_class("battery-peripheral");
+            if peripheral.percentage <= 20 {
+                item.add_css_class("low");
+            }
+            item.set_tooltip_text(Some(&peripheral.name));
+            peripheral_box.append(&item);
+        }
+        peripheral_box.set_visible(!peripherals.is_empty());
+    }
+
+    fn update_view(label: &Label, container: &Box, info: &BatteryInfo, config: &BatteryConfig) {
+        let percentage = info.percentage;
+
         // Hapus semua class CSS dulu (reset state)
-        container.remove_css_class("charging");
-        container.remove_css_class("critical");
-        container.remove_css_class("low");
+        for class in ["charging", "discharging", "full", "not-charging", "ac", "critical", "low"] {
+            container.remove_css_class(class);
+        }
+        for class in ["profile-performance", "profile-balanced", "profile-power-saver"] {
+            container.remove_css_class(class);
+        }
+        if let Some(profile) = &info.profile {
+            container.add_css_class(&format!("profile-{}", profile));
+        }
 
-        if percentage < 0 {
+        if !info.present {
             // Mode Desktop (Tanpa Baterai)
+            container.add_css_class("ac");
+            container.set_tooltip_text(None);
             label.set_text(" AC");
             return;
         }
 
-        let status = Self::get_status();
-        let icon = Self::get_icon(percentage, &status);
+        let icon = Self::get_icon(percentage, info.state);
+        container.set_tooltip_text(Some(&Self::tooltip_text(info)));
 
         // Update Teks: " 98%"
-        label.set_text(&format!("{} {}%", icon, percentage));
+        label.set_text(&Self::render_format(&config.format, icon, info));
 
-        // Logic pewarnaan CSS
-        if status == "Charging" {
-            container.add_css_class("charging");
-        } else if percentage <= 15 {
-            container.add_css_class("critical");
-        } else if percentage <= 30 {
-            container.add_css_class("low");
+        // Logic pewarnaan CSS: low/critical hanya saat memakai baterai
+        if let Some(class) = info.state.css_class() {
+            container.add_css_class(class);
+        }
+        if info.state == BatteryState::Discharging || !info.ac_online {
+            if percentage <= config.critical_threshold {
+                container.add_css_class("critical");
+            } else if percentage <= config.low_threshold {
+                container.add_css_class("low");
+            }
         }
     }
 }
@@ -92,15 +267,74 @@ impl WidgetModule for BatteryModule {
         let label = Label::new(None);
         container.append(&label);
 
-        let label_clone = label.clone();
-        let container_clone = container.clone();
+        // Baterai peripheral (opsional), diisi ulang setiap update
+        let peripheral_box = Box::new(Orientation::Horizontal, 8);
+        peripheral_box.add_css_class("battery-peripherals");
+        peripheral_box.set_visible(false);
+        container.append(&peripheral_box);
+        let show_peripherals = self.config.peripherals;
+
+        // Klik -> popover power profile & batas pengisian
+        let controls = BatteryControls::new(&container);
+        let click = GestureClick::new();
+        click.connect_pressed(move |gesture, _, _, _| {
+            if !popover::from_popover(gesture) {
+                controls.open();
+            }
+        });
+        container.add_controller(click);
+        let config = self.config.clone();
+        let mut alerts = Alerts::new(self.config.clone());
+        // Masa tenggang aksi kritis harus tetap berjalan walau UPower diam
+        let tick = self.config.critical_action.as_ref().map(|_| Duration::from_secs(10));
 
-        // Render awal
-        Self::update_view(&label, &container);
+        let (sender, receiver) = mpsc::channel();
+        let container_weak = container.downgrade();
 
-        // Update setiap 5 detik (Baterai tidak perlu dicek tiap milidetik)
-        glib::timeout_add_seconds_local(5, move || {
-            Self::update_view(&label_clone, &container_clone);
+        thread::spawn(move || {
+            let mut warned = false;
+            let mut estimator = Estimator::default();
+            let mut enrich = |info: &mut BatteryInfo| {
+                estimator.apply(info);
+                alerts.check(info);
+            };
+
+            loop {
+                // Utama: UPower D-Bus (event-driven, colok/cabut charger langsung terlihat)
+                match upower::run(&sender, tick, &mut enrich) {
+                    Ok(()) => break,
+                    Err(e) => {
+                        if !warned {
+                            eprintln!("[Battery] UPower tidak tersedia, pakai sysfs: {}", e);
+                            warned = true;
+                        }
+                    }
+                }
+
+                // Fallback sysfs: cek setiap 5 detik, coba konek UPower lagi tiap ~30 detik.
+                // power-profiles-daemon bisa tetap berjalan walau UPower tidak ada.
+                let system_bus = Connection::new_system().ok();
+                for _ in 0..6 {
+                    let mut info = sysfs::fetch();
+                    info.profile = system_bus.as_ref().and_then(profiles::active);
+                    enrich(&mut info);
+                    if sender.send(info).is_err() {
+                        return;
+                    }
+                    thread::sleep(Duration::from_secs(5));
+                }
+            }
+        });
+
+        glib::timeout_add_local(Duration::from_millis(100), move || {
+            if let Some(container) = container_weak.upgrade() {
+                if let Some(info) = receiver.try_iter().last() {
+                    Self::update_view(&label, &container, &info, &config);
+                    if show_peripherals {
+                        Self::update_peripherals(&peripheral_box, &info.peripherals);
+                    }
+                }
+            }
             glib::ControlFlow::Continue
         });
 
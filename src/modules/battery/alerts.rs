@@ -0,0 +1,133 @@
+// Peringatan baterai lemah/kritis + aksi otomatis (suspend/hibernate via logind).
+// Jalan di worker thread: notifikasi hanya dikirim sekali setiap melewati ambang,
+// dan baru aktif lagi setelah charger dicolok atau persen naik lagi.
+use super::{BatteryInfo, BatteryState};
+use crate::config::BatteryConfig;
+use crate::notify::{self, Urgency};
+use dbus::blocking::Connection;
+use std::time::{Duration, Instant};
+
+const LOGIN1_BUS: &str = "org.freedesktop.login1";
+const LOGIN1_PATH: &str = "/org/freedesktop/login1";
+const LOGIN1_MANAGER: &str = "org.freedesktop.login1.Manager";
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Level {
+    Normal,
+    Low,
+    Critical,
+}
+
+pub struct Alerts {
+    config: BatteryConfig,
+    level: Level,
+    action_deadline: Option<Instant>, // Aksi kritis dijalankan setelah masa tenggang
+    // Kapan aksi terakhir dijalankan. Instant (monotonic) tidak jalan selama suspend,
+    // jadi kalau setelah bangun baterai masih di bawah ambang dan tetap tidak dicas,
+    // aksi disiapkan ulang setelah satu masa tenggang lagi.
+    action_done: Option<Instant>,
+    notification: u32, // Id notifikasi terakhir (ditimpa, bukan ditumpuk)
+}
+
+impl Alerts {
+    pub fn new(config: BatteryConfig) -> Self {
+        Self {
+            config,
+            level: Level::Normal,
+            action_deadline: None,
+            action_done: None,
+            notification: 0,
+        }
+    }
+
+    fn notify(&mut self, summary: &str, body: &str, urgency: Urgency) {
+        if self.config.notify {
+            self.notification = notify::send(summary, body, "battery-caution", urgency, self.notification);
+        }
+    }
+
+    pub fn check(&mut self, info: &BatteryInfo) {
+        // Sedang dicas / penuh / tanpa baterai: reset semua peringatan
+        if !info.present || info.state != BatteryState::Discharging {
+            if self.action_deadline.take().is_some() {
+                self.notify("Baterai", "Charger terpasang, aksi dibatalkan", Urgency::Normal);
+            }
+            self.level = Level::Normal;
+            self.action_done = None;
+            return;
+        }
+
+        let percentage = info.percentage;
+        let level = if percentage <= self.config.critical_threshold {
+            Level::Critical
+        } else if percentage <= self.config.low_threshold {
+            Level::Low
+        } else {
+            Level::Normal
+        };
+
+        if level > self.level {
+            let body = format!("Sisa {}%, colokkan charger", percentage);
+            match level {
+                Level::Critical => self.notify("Baterai kritis", &body, Urgency::Critical),
+                Level::Low => self.notify("Baterai lemah", &body, Urgency::Normal),
+                Level::Normal => {}
+            }
+        }
+        self.level = level;
+
+        let action = match self.config.critical_action.clone() {
+            Some(a) => a,
+            None => return,
+        };
+
+        if percentage > self.config.action_threshold {
+            self.action_deadline = None;
+            self.action_done = None;
+            return;
+        }
+        let grace = self.config.action_grace_secs;
+        match self.action_done {
+            Some(done) if done.elapsed() < Duration::from_secs(grace) => return,
+            Some(_) => self.action_done = None,
+            None => {}
+        }
+
+        match self.action_deadline {
+            None => {
+                self.action_deadline = Some(Instant::now() + Duration::from_secs(grace));
+                let body = format!("Sisa {}%: {} dalam {} detik kecuali charger dicolok", percentage, action, grace);
+                self.notify("Baterai hampir habis", &body, Urgency::Critical);
+            }
+            Some(deadline) if Instant::now() >= deadline => {
+                self.action_deadline = None;
+                self.action_done = Some(Instant::now());
+                Self::run_action(&action);
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Suspend/hibernate lewat logind (tanpa spawn systemctl)
+    fn run_action(action: &str) {
+        let method = match action {
+            "suspend" => "Suspend",
+            "hibernate" => "Hibernate",
+            "hybrid-sleep" => "HybridSleep",
+            "poweroff" => "PowerOff",
+            _ => {
+                eprintln!("[Battery] critical_action '{}' tidak dikenal", action);
+                return;
+            }
+        };
+
+        let result = Connection::new_system().and_then(|conn| {
+            conn.with_proxy(LOGIN1_BUS, LOGIN1_PATH, Duration::from_secs(5))
+                // interactive = false: jangan minta password polkit
+                .method_call::<(), _, _, _>(LOGIN1_MANAGER, method, (false,))
+        });
+        if let Err(e) = result {
+            eprintln!("[Battery] Gagal menjalankan {}: {}", action, e);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+// Popover kontrol daya: pilih power profile dan batas pengisian baterai.
+// Semua panggilan D-Bus / tulis sysfs jalan di worker thread.
+use super::profiles;
+use crate::popover;
+use gtk4::prelude::*;
+use gtk4::{Box, Button, Label, Orientation, Popover, SpinButton, ToggleButton};
+use dbus::blocking::Connection;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+// Perintah dari UI ke worker
+enum ControlCommand {
+    Refresh,
+    SetProfile(String),
+    SetChargeLimit(u32),
+}
+
+// Balasan worker ke UI
+enum ControlEvent {
+    State {
+        profiles: Vec<String>,
+        active: String,
+        charge_limit: Option<u32>,
+    },
+    Error(String),
+}
+
+pub struct BatteryControls {
+    popover: Popover,
+    commands: mpsc::Sender<ControlCommand>,
+}
+
+impl BatteryControls {
+    pub fn new(parent: &Box) -> Rc<Self> {
+        let (cmd_sender, cmd_receiver) = mpsc::channel();
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        thread::spawn(move || Self::run_worker(cmd_receiver, event_sender));
+
+        let content = Box::new(Orientation::Vertical, 8);
+        content.add_css_class("battery-controls");
+
+        // --- POWER PROFILE ---
+        let profile_title = Label::new(Some("Power Profile"));
+        profile_title.add_css_class("battery-controls-title");
+        profile_title.set_halign(gtk4::Align::Start);
+        let profile_box = Box::new(Orientation::Horizontal, 4);
+        profile_box.set_homogeneous(true);
+
+        // --- BATAS PENGISIAN (hanya kalau didukung) ---
+        let limit_box = Box::new(Orientation::Vertical, 5);
+        limit_box.set_visible(false);
+        let limit_title = Label::new(Some("Batas Pengisian"));
+        limit_title.add_css_class("battery-controls-title");
+        limit_title.set_halign(gtk4::Align::Start);
+        let limit_row = Box::new(Orientation::Horizontal, 8);
+        let limit_spin = SpinButton::with_range(50.0, 100.0, 5.0);
+        limit_spin.set_hexpand(true);
+        let apply_btn = Button::with_label("Terapkan");
+        apply_btn.add_css_class("battery-controls-btn");
+        limit_row.append(&limit_spin);
+        limit_row.append(&apply_btn);
+        limit_box.append(&limit_title);
+        limit_box.append(&limit_row);
+
+        let status = Label::new(None);
+        status.add_css_class("battery-controls-status");
+        status.set_visible(false);
+        status.set_wrap(true);
+
+        content.append(&profile_title);
+        content.append(&profile_box);
+        content.append(&limit_box);
+        content.append(&status);
+
+        let popover = Popover::new();
+        popover.set_child(Some(&content));
+        popover.set_parent(parent);
+
+        let s = cmd_sender.clone();
+        let spin = limit_spin.clone();
+        apply_btn.connect_clicked(move |_| {
+            let _ = s.send(ControlCommand::SetChargeLimit(spin.value() as u32));
+        });
+
+        // fill_profiles() memanggil set_active() pada tombol profil; selama itu
+        // sinyal toggled diabaikan supaya tidak mengirim SetProfile balik ke worker
+        let updating = Rc::new(Cell::new(false));
+
+        // Terima hasil dari worker
+        let popover_weak = popover.downgrade();
+        let s = cmd_sender.clone();
+        glib::timeout_add_local(Duration::from_millis(100), move || {
+            if popover_weak.upgrade().is_none() {
+                return glib::ControlFlow::Break;
+            }
+            while let Ok(event) = event_receiver.try_recv() {
+                match event {
+                    ControlEvent::State { profiles, active, charge_limit } => {
+                        let unsupported = profiles.is_empty() && charge_limit.is_none();
+                        status.set_text("power-profiles-daemon tidak berjalan & batas pengisian tidak didukung");
+                        status.set_visible(unsupported);
+                        profile_title.set_visible(!profiles.is_empty());
+                        Self::fill_profiles(&profile_box, &profiles, &active, &s, &updating);
+
+                        limit_box.set_visible(charge_limit.is_some());
+                        if let Some(limit) = charge_limit {
+                            limit_spin.set_value(limit as f64);
+                        }
+                    }
+                    ControlEvent::Error(e) => {
+                        status.set_text(&e);
+                        status.set_visible(true);
+                    }
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+
+        Rc::new(Self { popover, commands: cmd_sender })
+    }
+
+    pub fn open(&self) {
+        let _ = self.commands.send(ControlCommand::Refresh);
+        self.popover.popup();
+    }
+
+    fn profile_label(profile: &str) -> String {
+        match profile {
+            "performance" => "󰓅 Performance".to_string(),
+            "balanced" => "󰾅 Balanced".to_string(),
+            "power-saver" => "󰾆 Power Saver".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    fn fill_profiles(
+        profile_box: &Box,
+        profiles: &[String],
+        active: &str,
+        commands: &mpsc::Sender<ControlCommand>,
+        updating: &Rc<Cell<bool>>,
+    ) {
+        while let Some(child) = profile_box.first_child() {
+            profile_box.remove(&child);
+        }
+
+        updating.set(true);
+        let mut group: Option<ToggleButton> = None;
+        for profile in profiles {
+            let button = ToggleButton::with_label(&Self::profile_label(profile));
+            button.add_css_class("battery-profile-btn");
+            // Satu grup = hanya satu yang aktif (seperti radio button)
+            if let Some(first) = &group {
+                button.set_group(Some(first));
+            } else {
+                group = Some(button.clone());
+            }
+            button.set_active(profile == active);
+
+            let s = commands.clone();
+            let guard = updating.clone();
+            let name = profile.clone();
+            button.connect_toggled(move |btn| {
+                if btn.is_active() && !guard.get() {
+                    let _ = s.send(ControlCommand::SetProfile(name.clone()));
+                }
+            });
+
+            profile_box.append(&button);
+        }
+        updating.set(false);
+    }
+
+    // Worker: satu koneksi system bus untuk semua aksi
+    fn run_worker(receiver: mpsc::Receiver<ControlCommand>, sender: mpsc::Sender<ControlEvent>) {
+        let conn = match Connection::new_system() {
+            Ok(c) => c,
+            Err(e) => {
+                let _ = sender.send(ControlEvent::Error(e.to_string()));
+                return;
+            }
+        };
+
+        for command in receiver {
+            let result = match command {
+                ControlCommand::Refresh => Ok(()),
+                ControlCommand::SetProfile(profile) => profiles::set(&conn, &profile)
+                    .map_err(|e| e.message().unwrap_or("power-profiles-daemon error").to_string()),
+                ControlCommand::SetChargeLimit(percent) => profiles::set_charge_limit(percent),
+            };
+
+            // power-profiles-daemon tidak wajib ada, batas pengisian tetap bisa dipakai
+            let (names, active) = profiles::list(&conn).unwrap_or_default();
+            let event = ControlEvent::State {
+                profiles: names,
+                active,
+                charge_limit: profiles::charge_limit(),
+            };
+            if !popover::send_state(&sender, event, result.err(), ControlEvent::Error) {
+                return;
+            }
+        }
+    }
+}
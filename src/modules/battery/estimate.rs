@@ -0,0 +1,46 @@
+// Perkiraan sisa waktu dari energi & daya. Daya dihaluskan (EMA) antar sampel
+// supaya angka "sisa 3j 10m" tidak loncat-loncat setiap ada lonjakan beban.
+use super::{BatteryInfo, BatteryState};
+
+// Bobot sampel baru (0..1), makin kecil makin halus
+const SMOOTHING: f64 = 0.3;
+
+#[derive(Default)]
+pub struct Estimator {
+    rate: Option<f64>, // Watt, hasil smoothing
+    state: Option<BatteryState>,
+}
+
+impl Estimator {
+    pub fn apply(&mut self, info: &mut BatteryInfo) {
+        // Charger dicolok/dicabut: arah arus berubah, mulai dari awal
+        if self.state != Some(info.state) {
+            self.state = Some(info.state);
+            self.rate = None;
+        }
+        if !info.present || info.energy_rate <= 0.0 {
+            self.rate = None;
+            return;
+        }
+
+        let rate = match self.rate {
+            Some(prev) => prev + SMOOTHING * (info.energy_rate - prev),
+            None => info.energy_rate,
+        };
+        self.rate = Some(rate);
+        info.energy_rate = rate;
+
+        // Tanpa data energi, pakai perkiraan dari backend (UPower) apa adanya
+        if info.energy_full <= 0.0 {
+            return;
+        }
+        match info.state {
+            BatteryState::Discharging => info.time_to_empty = (info.energy_now / rate * 3600.0) as i64,
+            BatteryState::Charging => {
+                let missing = (info.energy_full - info.energy_now).max(0.0);
+                info.time_to_full = (missing / rate * 3600.0) as i64;
+            }
+            _ => {}
+        }
+    }
+}
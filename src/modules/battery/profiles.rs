@@ -0,0 +1,96 @@
+// Power profile via power-profiles-daemon (D-Bus) dan batas pengisian
+// (charge_control_end_threshold) via sysfs.
+use dbus::arg::{prop_cast, PropMap};
+use dbus::blocking::Connection;
+use dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Nama bus baru (ppd >= 0.20) dan lama; nama interface sama dengan nama bus
+pub const PPD_BUSES: [(&str, &str); 2] = [
+    ("org.freedesktop.UPower.PowerProfiles", "/org/freedesktop/UPower/PowerProfiles"),
+    ("net.hadess.PowerProfiles", "/net/hadess/PowerProfiles"),
+];
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+// Profile yang sedang aktif, None kalau power-profiles-daemon tidak berjalan
+pub fn active(conn: &Connection) -> Option<String> {
+    PPD_BUSES.iter().find_map(|(bus, path)| {
+        conn.with_proxy(*bus, *path, TIMEOUT).get::<String>(bus, "ActiveProfile").ok()
+    })
+}
+
+// Semua profile yang didukung mesin ini + profile aktif
+pub fn list(conn: &Connection) -> Result<(Vec<String>, String), dbus::Error> {
+    let mut last_error = None;
+    for (bus, path) in PPD_BUSES {
+        let proxy = conn.with_proxy(bus, path, TIMEOUT);
+        let active = match proxy.get::<String>(bus, "ActiveProfile") {
+            Ok(a) => a,
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            }
+        };
+        let profiles: Vec<PropMap> = proxy.get(bus, "Profiles")?;
+        let names = profiles.iter()
+            .filter_map(|p| prop_cast::<String>(p, "Profile").cloned())
+            .collect();
+        return Ok((names, active));
+    }
+    Err(last_error.unwrap_or_else(|| dbus::Error::new_failed("power-profiles-daemon tidak ditemukan")))
+}
+
+pub fn set(conn: &Connection, profile: &str) -> Result<(), dbus::Error> {
+    let mut last_error = None;
+    for (bus, path) in PPD_BUSES {
+        match conn.with_proxy(bus, path, TIMEOUT).set(bus, "ActiveProfile", profile.to_string()) {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| dbus::Error::new_failed("power-profiles-daemon tidak ditemukan")))
+}
+
+// File batas pengisian milik baterai sistem (tidak semua laptop punya)
+fn threshold_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir("/sys/class/power_supply")
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path().join("charge_control_end_threshold"))
+                .filter(|p| p.exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// Batas pengisian (%) dari baterai pertama, None kalau tidak didukung
+pub fn charge_limit() -> Option<u32> {
+    let file = threshold_files().into_iter().next()?;
+    fs::read_to_string(file).ok()?.trim().parse().ok()
+}
+
+// Tulis ke semua baterai supaya laptop dua baterai konsisten.
+// Butuh izin tulis (root / aturan udev), pesan error menjelaskan itu.
+pub fn set_charge_limit(percent: u32) -> Result<(), String> {
+    let files = threshold_files();
+    if files.is_empty() {
+        return Err("Batas pengisian tidak didukung".to_string());
+    }
+
+    for file in files {
+        if let Err(e) = fs::write(&file, percent.to_string()) {
+            return Err(match e.kind() {
+                ErrorKind::PermissionDenied => {
+                    format!("Tidak ada izin menulis {} (butuh aturan udev)", file.display())
+                }
+                _ => e.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,185 @@
+// Fallback tanpa UPower: baca langsung /sys/class/power_supply.
+// Semua device bertipe Battery dihitung (BAT0, BAT1, BATT, CMB0, ...),
+// baterai perangkat (scope=Device) dipisah sebagai peripheral.
+use super::{BatteryInfo, BatteryState, Peripheral};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+fn read(dir: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(dir.join(file)).ok().map(|s| s.trim().to_string())
+}
+
+fn read_f64(dir: &Path, file: &str) -> Option<f64> {
+    read(dir, file)?.parse().ok()
+}
+
+// Energi & daya satu baterai dalam Wh / W
+struct Energy {
+    now: f64,
+    full: f64,
+    design: f64, // 0 = tidak dilaporkan
+    power: f64,
+}
+
+// Driver yang hanya punya charge_* (µAh) & current_now (µA) dikonversi
+// pakai tegangan supaya bisa dijumlah dengan baterai lain.
+fn read_energy(dir: &Path) -> Option<Energy> {
+    if let (Some(now), Some(full)) = (read_f64(dir, "energy_now"), read_f64(dir, "energy_full")) {
+        return Some(Energy {
+            now: now / 1e6,
+            full: full / 1e6,
+            design: read_f64(dir, "energy_full_design").unwrap_or(0.0) / 1e6,
+            // Sebagian driver memberi nilai negatif saat discharging
+            power: read_f64(dir, "power_now").unwrap_or(0.0).abs() / 1e6,
+        });
+    }
+
+    let now = read_f64(dir, "charge_now")?;
+    let full = read_f64(dir, "charge_full")?;
+    let volts = read_f64(dir, "voltage_min_design")
+        .or_else(|| read_f64(dir, "voltage_now"))?
+        / 1e6;
+    let current_volts = read_f64(dir, "voltage_now").map(|v| v / 1e6).unwrap_or(volts);
+    Some(Energy {
+        now: now / 1e6 * volts,
+        full: full / 1e6 * volts,
+        design: read_f64(dir, "charge_full_design").unwrap_or(0.0) / 1e6 * volts,
+        power: read_f64(dir, "current_now").unwrap_or(0.0).abs() / 1e6 * current_volts,
+    })
+}
+
+// Sebagian peripheral hanya melaporkan capacity_level (tanpa angka)
+fn level_to_percent(level: &str) -> Option<i32> {
+    match level {
+        "Full" => Some(100),
+        "High" => Some(80),
+        "Normal" => Some(50),
+        "Low" => Some(20),
+        "Critical" => Some(5),
+        _ => None,
+    }
+}
+
+// Tebak jenis peripheral dari nama model (sysfs tidak punya tipe device)
+fn peripheral_icon(model: &str) -> &'static str {
+    let model = model.to_lowercase();
+    if model.contains("mouse") || model.contains("trackball") {
+        "󰍽"
+    } else if model.contains("keyboard") {
+        "󰌌"
+    } else if model.contains("headset") || model.contains("headphone") || model.contains("buds") {
+        "󰋋"
+    } else if model.contains("controller") || model.contains("gamepad") {
+        "󰊴"
+    } else {
+        "󰁹"
+    }
+}
+
+// Adaptor AC / USB-C yang sedang memberi daya
+fn ac_online(entries: &[PathBuf]) -> bool {
+    entries.iter().any(|dir| {
+        let is_adapter = matches!(read(dir, "type").as_deref(), Some(t) if t == "Mains" || t.starts_with("USB"));
+        is_adapter && read(dir, "online").as_deref() == Some("1")
+    })
+}
+
+pub fn fetch() -> BatteryInfo {
+    let mut info = BatteryInfo::absent();
+    let mut states = Vec::new();
+    let mut capacities = Vec::new();
+    let (mut energy_now, mut energy_full, mut energy_design, mut power) = (0.0, 0.0, 0.0, 0.0);
+    let mut all_design = true;
+    let mut all_energy = true;
+
+    let mut entries: Vec<_> = match fs::read_dir(POWER_SUPPLY_DIR) {
+        Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+        Err(_) => return info,
+    };
+    entries.sort();
+    info.ac_online = ac_online(&entries);
+
+    for dir in entries {
+        if read(&dir, "type").as_deref() != Some("Battery") || read(&dir, "present").as_deref() == Some("0") {
+            continue;
+        }
+
+        let name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let capacity = read(&dir, "capacity").and_then(|c| c.parse::<i32>().ok());
+
+        if read(&dir, "scope").as_deref() == Some("Device") {
+            let percentage = capacity.or_else(|| level_to_percent(&read(&dir, "capacity_level")?));
+            if let Some(percentage) = percentage {
+                let model = read(&dir, "model_name").filter(|m| !m.is_empty()).unwrap_or(name);
+                info.peripherals.push(Peripheral { icon: peripheral_icon(&model), name: model, percentage });
+            }
+            continue;
+        }
+
+        let capacity = match capacity {
+            Some(c) => c,
+            None => continue,
+        };
+        match read_energy(&dir) {
+            Some(energy) if energy.full > 0.0 => {
+                energy_now += energy.now;
+                energy_full += energy.full;
+                energy_design += energy.design;
+                power += energy.power;
+                all_design &= energy.design > 0.0;
+            }
+            _ => all_energy = false,
+        }
+
+        if let Some(cycles) = read(&dir, "cycle_count").and_then(|c| c.parse::<i32>().ok()) {
+            // 0 = driver tidak menghitung siklus
+            if cycles > 0 {
+                info.cycles = Some(info.cycles.map_or(cycles, |c| c.max(cycles)));
+            }
+        }
+        capacities.push(capacity);
+        states.push(BatteryState::from_sysfs(&read(&dir, "status").unwrap_or_default()));
+        info.batteries.push((name, capacity));
+    }
+
+    if capacities.is_empty() {
+        return info;
+    }
+
+    // Gabungan berbobot energi: baterai besar yang 20% lebih berarti dari baterai kecil 80%.
+    // Kalau ada baterai tanpa data energi, pakai rata-rata capacity.
+    info.present = true;
+    info.percentage = if all_energy && energy_full > 0.0 {
+        (energy_now / energy_full * 100.0).round() as i32
+    } else {
+        capacities.iter().sum::<i32>() / capacities.len() as i32
+    };
+
+    // Data energi hanya dipakai kalau lengkap untuk semua baterai
+    if all_energy {
+        info.energy_now = energy_now;
+        info.energy_full = energy_full;
+        info.energy_rate = power;
+        if all_design && energy_design > 0.0 {
+            info.health = Some(energy_full / energy_design * 100.0);
+        }
+    }
+
+    // Satu baterai mengisi = charging; semua penuh = full
+    let has = |state| states.contains(&state);
+    info.state = if has(BatteryState::Charging) {
+        BatteryState::Charging
+    } else if has(BatteryState::Discharging) {
+        BatteryState::Discharging
+    } else if states.iter().all(|s| *s == BatteryState::Full) {
+        BatteryState::Full
+    } else if has(BatteryState::NotCharging) || info.ac_online {
+        // Sebagian driver melapor "Unknown" saat ditahan di batas pengisian
+        BatteryState::NotCharging
+    } else {
+        BatteryState::Unknown
+    };
+    info
+}
@@ -0,0 +1,194 @@
+// Backend UPower via D-Bus (system bus).
+// Data diambil dari DisplayDevice (gabungan semua baterai versi UPower)
+// dan di-refresh hanya saat UPower mengirim sinyal perubahan.
+use super::profiles;
+use super::{BatteryInfo, BatteryState, Peripheral};
+use dbus::arg::{prop_cast, PropMap};
+use dbus::blocking::Connection;
+use dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+use dbus::channel::MatchingReceiver;
+use dbus::message::MatchRule;
+use dbus::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+const UPOWER_BUS: &str = "org.freedesktop.UPower";
+const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+const UPOWER_IFACE: &str = "org.freedesktop.UPower";
+const DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const DEVICE_IFACE: &str = "org.freedesktop.UPower.Device";
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+// Nilai properti State (UpDeviceState)
+fn parse_state(state: u32) -> BatteryState {
+    match state {
+        1 => BatteryState::Charging,
+        2 | 3 | 6 => BatteryState::Discharging, // 3 = empty, 6 = pending discharge
+        4 => BatteryState::Full,
+        5 => BatteryState::NotCharging, // Pending charge: AC terpasang, menunggu/ditahan
+        _ => BatteryState::Unknown,
+    }
+}
+
+fn parse_device(props: &PropMap) -> BatteryInfo {
+    let present = prop_cast::<bool>(props, "IsPresent").copied().unwrap_or(false);
+    if !present {
+        return BatteryInfo::absent();
+    }
+
+    let state = prop_cast::<u32>(props, "State").copied().unwrap_or(0);
+    BatteryInfo {
+        present,
+        percentage: prop_cast::<f64>(props, "Percentage").copied().unwrap_or(0.0).round() as i32,
+        state: parse_state(state),
+        time_to_empty: prop_cast::<i64>(props, "TimeToEmpty").copied().unwrap_or(0),
+        time_to_full: prop_cast::<i64>(props, "TimeToFull").copied().unwrap_or(0),
+        energy_rate: prop_cast::<f64>(props, "EnergyRate").copied().unwrap_or(0.0),
+        energy_now: prop_cast::<f64>(props, "Energy").copied().unwrap_or(0.0),
+        energy_full: prop_cast::<f64>(props, "EnergyFull").copied().unwrap_or(0.0),
+        ..BatteryInfo::absent()
+    }
+}
+
+// Ikon dari properti Type (UpDeviceKind)
+fn peripheral_icon(kind: u32) -> &'static str {
+    match kind {
+        5 | 14 => "󰍽",       // Mouse, touchpad
+        6 => "󰌌",            // Keyboard
+        12 => "󰊴",           // Gaming input
+        17 | 19 => "󰋋",      // Headset, headphones
+        8 => "󰏲",            // Phone
+        13 => "󰏪",           // Pen
+        _ => "󰁹",
+    }
+}
+
+fn fetch(conn: &Connection) -> Result<BatteryInfo, dbus::Error> {
+    // DisplayDevice sudah menggabungkan semua baterai sistem (berbobot energi)
+    let props = conn.with_proxy(UPOWER_BUS, DISPLAY_DEVICE_PATH, TIMEOUT).get_all(DEVICE_IFACE)?;
+    let mut info = parse_device(&props);
+
+    // OnBattery = tidak ada sumber listrik luar sama sekali
+    let on_battery: bool = conn.with_proxy(UPOWER_BUS, UPOWER_PATH, TIMEOUT)
+        .get(UPOWER_IFACE, "OnBattery")
+        .unwrap_or(false);
+    info.ac_online = !on_battery;
+
+    let (devices,): (Vec<Path<'static>>,) = conn.with_proxy(UPOWER_BUS, UPOWER_PATH, TIMEOUT)
+        .method_call(UPOWER_IFACE, "EnumerateDevices", ())?;
+
+    // DisplayDevice tidak punya EnergyFullDesign, jadi kesehatan dihitung dari
+    // jumlah kapasitas semua baterai sistem
+    let (mut energy_full, mut energy_design) = (0.0, 0.0);
+    for device in devices {
+        let props = match conn.with_proxy(UPOWER_BUS, device.clone(), TIMEOUT).get_all(DEVICE_IFACE) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        let kind = prop_cast::<u32>(&props, "Type").copied().unwrap_or(0);
+        let power_supply = prop_cast::<bool>(&props, "PowerSupply").copied().unwrap_or(false);
+        let percentage = prop_cast::<f64>(&props, "Percentage").copied().unwrap_or(0.0).round() as i32;
+        let native_path = prop_cast::<String>(&props, "NativePath").cloned().unwrap_or_default();
+
+        if kind == 2 && power_supply {
+            // Baterai sistem: nama sysfs (BAT0, BAT1, CMB0)
+            let name = native_path.rsplit('/').next().unwrap_or_default().to_string();
+            info.batteries.push((name, percentage));
+
+            let design = prop_cast::<f64>(&props, "EnergyFullDesign").copied().unwrap_or(0.0);
+            if design > 0.0 {
+                energy_full += prop_cast::<f64>(&props, "EnergyFull").copied().unwrap_or(0.0);
+                energy_design += design;
+            }
+
+            // -1 = driver tidak melaporkan
+            let cycles = prop_cast::<i32>(&props, "ChargeCycles").copied().unwrap_or(-1);
+            if cycles >= 0 {
+                info.cycles = Some(info.cycles.map_or(cycles, |c| c.max(cycles)));
+            }
+        } else if !power_supply && kind != 1 {
+            // Bukan line power & tidak menyuplai sistem = baterai peripheral
+            let name = prop_cast::<String>(&props, "Model")
+                .filter(|m| !m.is_empty())
+                .cloned()
+                .unwrap_or(native_path);
+            info.peripherals.push(Peripheral { icon: peripheral_icon(kind), name, percentage });
+        }
+    }
+
+    info.health = (energy_design > 0.0).then(|| energy_full / energy_design * 100.0);
+    info.profile = profiles::active(conn);
+    Ok(info)
+}
+
+// Listener: blok menunggu sinyal UPower. Return Err kalau UPower tidak ada /
+// bus putus, supaya pemanggil bisa pindah ke fallback sysfs.
+// `tick` (opsional) membangunkan loop secara berkala walau tidak ada sinyal,
+// `enrich` melengkapi info (perkiraan waktu, peringatan) sebelum dikirim.
+pub fn run(
+    sender: &mpsc::Sender<BatteryInfo>,
+    tick: Option<Duration>,
+    enrich: &mut dyn FnMut(&mut BatteryInfo),
+) -> Result<(), dbus::Error> {
+    let conn = Connection::new_system()?;
+
+    // Fetch pertama sekaligus cek apakah UPower berjalan
+    let mut info = fetch(&conn)?;
+    enrich(&mut info);
+    if sender.send(info).is_err() {
+        return Ok(());
+    }
+
+    let dirty = Arc::new(AtomicBool::new(false));
+
+    // PropertiesChanged: persen, state, charger dicolok/dicabut (OnBattery), dll
+    let props_rule = MatchRule::new_signal("org.freedesktop.DBus.Properties", "PropertiesChanged")
+        .with_sender(UPOWER_BUS);
+    // Baterai ditambah/dilepas (misal laptop dengan baterai hot-swap)
+    let added_rule = MatchRule::new_signal(UPOWER_IFACE, "DeviceAdded")
+        .with_sender(UPOWER_BUS)
+        .with_path(UPOWER_PATH);
+    let removed_rule = MatchRule::new_signal(UPOWER_IFACE, "DeviceRemoved")
+        .with_sender(UPOWER_BUS)
+        .with_path(UPOWER_PATH);
+
+    // Power profile berganti (dari popover atau tool lain) -> update class CSS
+    let profile_rules = profiles::PPD_BUSES.map(|(bus, _)| {
+        MatchRule::new_signal("org.freedesktop.DBus.Properties", "PropertiesChanged").with_sender(bus)
+    });
+
+    for rule in [props_rule, added_rule, removed_rule].into_iter().chain(profile_rules) {
+        conn.add_match_no_cb(&rule.match_str())?;
+
+        let dirty = dirty.clone();
+        conn.start_receive(rule, Box::new(move |_: dbus::Message, _: &Connection| {
+            dirty.store(true, Ordering::Relaxed);
+            true
+        }));
+    }
+
+    let mut next_tick = tick.map(|t| Instant::now() + t);
+
+    loop {
+        let wait = next_tick
+            .map(|t| t.saturating_duration_since(Instant::now()))
+            .unwrap_or(Duration::from_secs(60));
+        conn.process(wait)?;
+
+        let changed = dirty.swap(false, Ordering::Relaxed);
+        let ticked = next_tick.is_some_and(|t| Instant::now() >= t);
+        if !changed && !ticked {
+            continue;
+        }
+        if let (true, Some(t)) = (ticked, tick) {
+            next_tick = Some(Instant::now() + t);
+        }
+
+        let mut info = fetch(&conn)?;
+        enrich(&mut info);
+        if sender.send(info).is_err() {
+            return Ok(()); // Widget sudah dihancurkan
+        }
+    }
+}
@@ -1,23 +1,199 @@
 // Pastikan baris ini gtk4, BUKAN gtk
-use gtk4::prelude::*; 
-use gtk4::Label;
-use chrono::Local;
+use gtk4::prelude::*;
+use gtk4::{Box, GestureClick, Label, Orientation};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, Duration, Local, Utc};
+use chrono_tz::Tz;
 use super::WidgetModule;
+use crate::config::ClockConfig;
+use calendar::CalendarPopover;
+use crate::popover;
+use crate::text;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
-pub struct ClockModule;
+mod calendar;
+mod events;
+mod ics;
+pub mod tick;
+
+const DEFAULT_FORMAT: &str = "%H:%M:%S";
+const AGENDA_POLL_SECS: u32 = 5;
+
+pub struct ClockModule {
+    config: ClockConfig,
+}
+
+impl ClockModule {
+    pub fn new(config: ClockConfig) -> Self {
+        Self { config }
+    }
+
+    // Format strftime yang salah bikin chrono panic saat di-render, jadi dicek di awal
+    fn valid_format(format: &str) -> bool {
+        !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+    }
+
+    fn checked_format(format: &str, fallback: &str) -> String {
+        if Self::valid_format(format) {
+            format.to_string()
+        } else {
+            eprintln!("[Clock] Format '{}' tidak valid, pakai '{}'", format, fallback);
+            fallback.to_string()
+        }
+    }
+
+    fn parse_timezone(name: &Option<String>) -> Option<Tz> {
+        let name = name.as_ref()?;
+        match name.parse::<Tz>() {
+            Ok(tz) => Some(tz),
+            Err(_) => {
+                eprintln!("[Clock] Zona waktu '{}' tidak dikenal, pakai zona lokal", name);
+                None
+            }
+        }
+    }
+
+    // Waktu sekarang di zona instance (None = zona lokal sistem)
+    fn format_now(tz: Option<Tz>, format: &str) -> String {
+        match tz {
+            Some(tz) => Utc::now().with_timezone(&tz).format(format).to_string(),
+            None => Local::now().format(format).to_string(),
+        }
+    }
+
+    // Tanggal hari ini (tahun, bulan, hari) di zona instance, untuk kalender
+    fn today(tz: Option<Tz>) -> (i32, u32, u32) {
+        match tz {
+            Some(tz) => {
+                let now = Utc::now().with_timezone(&tz);
+                (now.year(), now.month(), now.day())
+            }
+            None => {
+                let now = Local::now();
+                (now.year(), now.month(), now.day())
+            }
+        }
+    }
+}
 
 impl WidgetModule for ClockModule {
     fn build_widget(&self) -> gtk4::Widget { // Pastikan return type-nya gtk4
+        let container = Box::new(Orientation::Horizontal, 0);
+        container.add_css_class("clock-widget");
+
         let label = Label::new(None);
-        label.add_css_class("clock-widget");
+        container.append(&label);
 
-        let label_clone = label.clone();
-        glib::timeout_add_seconds_local(1, move || {
-            let time = Local::now().format("%H:%M:%S").to_string();
-            label_clone.set_text(&time);
-            glib::ControlFlow::Continue
+        // "󰤙 Standup 10m", hanya terlihat kalau ada acara dekat
+        let next_label = Label::new(None);
+        next_label.add_css_class("clock-next-event");
+        next_label.set_visible(false);
+        container.append(&next_label);
+
+        let tz = Self::parse_timezone(&self.config.timezone);
+        let format = Self::checked_format(&self.config.format, DEFAULT_FORMAT);
+        let format_alt = self.config.format_alt.as_ref()
+            .map(|f| Self::checked_format(f, &format));
+        let tooltip_format = Self::checked_format(&self.config.tooltip_format, "%A, %d %B %Y");
+        let show_alt = Rc::new(Cell::new(false));
+
+        // Format tanpa detik cukup di-update tiap menit/jam
+        let mut formats = vec![format.as_str(), tooltip_format.as_str()];
+        formats.extend(format_alt.as_deref());
+        let mut period = tick::resolution(&formats);
+        let next_event = self.config.next_event && !self.config.calendars.is_empty();
+        if next_event {
+            // Hitung mundur acara butuh update tiap menit
+            period = period.min(60);
+        }
+        let within = Duration::minutes(self.config.next_event_mins);
+        let agenda = Rc::new(RefCell::new(Vec::new()));
+
+        let render: Rc<dyn Fn()> = {
+            let label = label.clone();
+            let container = container.clone();
+            let show_alt = show_alt.clone();
+            let format_alt = format_alt.clone();
+            let agenda = agenda.clone();
+            Rc::new(move || {
+                let current = match &format_alt {
+                    Some(alt) if show_alt.get() => alt,
+                    _ => &format,
+                };
+                label.set_text(&Self::format_now(tz, current));
+                container.set_tooltip_text(Some(&Self::format_now(tz, &tooltip_format)));
+
+                let upcoming = if next_event { events::upcoming(&agenda.borrow(), within).cloned() } else { None };
+                match upcoming {
+                    Some(event) => {
+                        let minutes = ((event.start - Local::now()).num_seconds() + 59) / 60;
+                        next_label.set_text(&format!("󰤙 {} {}m", text::truncate(&event.summary, 20), minutes));
+                        next_label.set_visible(true);
+                    }
+                    None => next_label.set_visible(false),
+                }
+            })
+        };
+        render();
+
+        let calendar = CalendarPopover::new(
+            &container,
+            self.config.week_numbers,
+            agenda.clone(),
+            !self.config.calendars.is_empty(),
+        );
+
+        // Agenda dibaca di worker thread, UI hanya menerima hasilnya
+        if !self.config.calendars.is_empty() {
+            let receiver = events::spawn(&self.config.calendars);
+            // Referensi lemah: widget yang sudah dihancurkan menghentikan polling ini
+            // (receiver ikut di-drop, worker berhenti di send berikutnya)
+            let widget = container.downgrade();
+            let calendar = Rc::downgrade(&calendar);
+            let render = Rc::downgrade(&render);
+            // Worker baru mengirim kalau file berubah (dicek tiap menit), jadi tidak perlu sering
+            glib::timeout_add_seconds_local(AGENDA_POLL_SECS, move || {
+                let (calendar, render) = match (widget.upgrade(), calendar.upgrade(), render.upgrade()) {
+                    (Some(_), Some(calendar), Some(render)) => (calendar, render),
+                    _ => return glib::ControlFlow::Break,
+                };
+                if let Some(loaded) = receiver.try_iter().last() {
+                    *agenda.borrow_mut() = loaded;
+                    calendar.refresh();
+                    render();
+                }
+                glib::ControlFlow::Continue
+            });
+        }
+
+        // Klik kiri -> kalender, klik kanan -> ganti ke format_alt (kalau ada)
+        let click = GestureClick::new();
+        click.set_button(0);
+        let render_click = render.clone();
+        click.connect_pressed(move |gesture, _, _, _| {
+            if popover::from_popover(gesture) {
+                return;
+            }
+            match gesture.current_button() {
+                1 => {
+                    let (year, month, day) = Self::today(tz);
+                    calendar.open(year, month, day);
+                }
+                3 if format_alt.is_some() => {
+                    show_alt.set(!show_alt.get());
+                    render_click();
+                }
+                _ => {}
+            }
         });
+        container.add_controller(click);
+
+        tick::start(tz, period, Rc::new(move || {
+            render();
+            glib::ControlFlow::Continue
+        }));
 
-        label.upcast()
+        container.upcast()
     }
-}
\ No newline at end of file
+}
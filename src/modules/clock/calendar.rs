@@ -0,0 +1,158 @@
+// Popover kalender bulanan. Hari pertama dalam minggu mengikuti locale (LC_TIME),
+// diatur sendiri oleh gtk4::Calendar. Kalau [clock] calendars diisi, hari yang
+// punya acara ditandai dan agenda hari terpilih ditampilkan di bawahnya.
+use super::events;
+use super::ics::Event;
+use chrono::{Datelike, NaiveDate};
+use gtk4::prelude::*;
+use gtk4::{Box, Button, Calendar, Label, Orientation, Popover};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+pub struct CalendarPopover {
+    popover: Popover,
+    calendar: Calendar,
+    agenda: Box,
+    today: Cell<(i32, u32, u32)>, // Diisi setiap open(), dipakai tombol "Hari ini"
+    events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl CalendarPopover {
+    pub fn new(parent: &Box, week_numbers: bool, events: Rc<RefCell<Vec<Event>>>, show_agenda: bool) -> Rc<Self> {
+        let content = Box::new(Orientation::Vertical, 8);
+        content.add_css_class("clock-calendar");
+
+        let calendar = Calendar::new();
+        calendar.set_show_week_numbers(week_numbers);
+
+        // Kembali ke hari ini setelah pindah-pindah bulan
+        let today_btn = Button::with_label("Hari ini");
+        today_btn.add_css_class("clock-calendar-btn");
+
+        let agenda = Box::new(Orientation::Vertical, 4);
+        agenda.add_css_class("clock-agenda");
+        agenda.set_visible(show_agenda);
+
+        content.append(&calendar);
+        content.append(&today_btn);
+        content.append(&agenda);
+
+        let popover = Popover::new();
+        popover.set_child(Some(&content));
+        popover.set_parent(parent);
+
+        let this = Rc::new(Self {
+            popover,
+            calendar,
+            agenda,
+            today: Cell::new((1970, 1, 1)),
+            events,
+        });
+
+        let weak = Rc::downgrade(&this);
+        today_btn.connect_clicked(move |_| {
+            if let Some(this) = weak.upgrade() {
+                this.select(this.today.get());
+            }
+        });
+
+        if show_agenda {
+            let weak = Rc::downgrade(&this);
+            this.calendar.connect_day_selected(move |_| {
+                if let Some(this) = weak.upgrade() {
+                    this.show_agenda();
+                }
+            });
+            // Tanda acara per bulan, diisi ulang saat pindah bulan/tahun
+            for property in ["month", "year"] {
+                let weak = Rc::downgrade(&this);
+                this.calendar.connect_notify_local(Some(property), move |_, _| {
+                    if let Some(this) = weak.upgrade() {
+                        this.mark_days();
+                    }
+                });
+            }
+        }
+
+        this
+    }
+
+    fn select(&self, (year, month, day): (i32, u32, u32)) {
+        if let Ok(date) = glib::DateTime::from_local(year, month as i32, day as i32, 0, 0, 0.0) {
+            self.calendar.select_day(&date);
+        }
+    }
+
+    fn selected_date(&self) -> Option<NaiveDate> {
+        let date = self.calendar.date();
+        NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day_of_month() as u32)
+    }
+
+    fn mark_days(&self) {
+        self.calendar.clear_marks();
+        let selected = match self.selected_date() {
+            Some(d) => d,
+            None => return,
+        };
+        let events = self.events.borrow();
+        let mut day = selected.with_day(1);
+        while let Some(date) = day.filter(|d| d.month() == selected.month()) {
+            if !events::on_day(&events, date).is_empty() {
+                self.calendar.mark_day(date.day());
+            }
+            day = date.succ_opt();
+        }
+    }
+
+    fn show_agenda(&self) {
+        while let Some(child) = self.agenda.first_child() {
+            self.agenda.remove(&child);
+        }
+        let date = match self.selected_date() {
+            Some(d) => d,
+            None => return,
+        };
+
+        let title = Label::new(Some(&date.format("Agenda %d/%m/%Y").to_string()));
+        title.add_css_class("clock-agenda-title");
+        title.set_halign(gtk4::Align::Start);
+        self.agenda.append(&title);
+
+        let events = self.events.borrow();
+        let day_events = events::on_day(&events, date);
+        if day_events.is_empty() {
+            let empty = Label::new(Some("Tidak ada acara"));
+            empty.add_css_class("clock-agenda-empty");
+            empty.set_halign(gtk4::Align::Start);
+            self.agenda.append(&empty);
+        }
+        for event in day_events {
+            let time = if event.all_day {
+                "Sepanjang hari".to_string()
+            } else {
+                format!("{}–{}", event.start.format("%H:%M"), event.end.format("%H:%M"))
+            };
+            let row = Label::new(Some(&format!("{}  {}", time, event.summary)));
+            row.add_css_class("clock-agenda-event");
+            row.set_halign(gtk4::Align::Start);
+            row.set_wrap(true);
+            self.agenda.append(&row);
+        }
+    }
+
+    // Dipanggil setelah worker selesai membaca ulang file kalender
+    pub fn refresh(&self) {
+        if self.agenda.is_visible() {
+            self.mark_days();
+            self.show_agenda();
+        }
+    }
+
+    // Tanggal dari zona waktu instance jam, bukan selalu zona lokal
+    pub fn open(&self, year: i32, month: u32, day: u32) {
+        self.today.set((year, month, day));
+        self.select((year, month, day));
+        self.refresh();
+        self.popover.popup();
+    }
+}
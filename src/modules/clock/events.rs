@@ -0,0 +1,100 @@
+// Agenda dari file .ics lokal atau folder vdir (hasil vdirsyncer), tanpa network.
+// File dibaca di worker thread dan hanya di-parse ulang kalau ada yang berubah
+// (daftar file / mtime) atau hari sudah berganti.
+use super::ics::{self, Event};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::SystemTime;
+
+const RESCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+// Rentang kejadian yang dijabarkan (cukup untuk navigasi kalender beberapa bulan)
+const DAYS_BEFORE: i64 = 62;
+const DAYS_AFTER: i64 = 400;
+
+// "~/..." -> $HOME/...
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// vdir: <akun>/<kalender>/<event>.ics, jadi turun maksimal dua level
+fn collect_files(path: &Path, depth: u32, files: &mut Vec<(PathBuf, Option<SystemTime>)>) {
+    if path.is_dir() {
+        if depth == 0 {
+            return;
+        }
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                collect_files(&entry.path(), depth - 1, files);
+            }
+        }
+    } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ics")) {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        files.push((path.to_path_buf(), modified));
+    }
+}
+
+fn load(files: &[(PathBuf, Option<SystemTime>)], from: DateTime<Local>, to: DateTime<Local>) -> Vec<Event> {
+    let mut events: Vec<Event> = files.iter()
+        .filter_map(|(path, _)| fs::read_to_string(path).ok())
+        .flat_map(|content| ics::parse(&content, from, to))
+        .collect();
+    events.sort_by_key(|e| e.start);
+    events
+}
+
+// Worker berhenti sendiri setelah receiver di-drop (send gagal)
+pub fn spawn(paths: &[String]) -> mpsc::Receiver<Vec<Event>> {
+    let (sender, receiver) = mpsc::channel();
+    let roots: Vec<PathBuf> = paths.iter().map(|p| expand_home(p)).collect();
+
+    thread::spawn(move || {
+        let mut last_files = None;
+        let mut last_day: Option<NaiveDate> = None;
+        loop {
+            let mut files = Vec::new();
+            for root in &roots {
+                collect_files(root, 2, &mut files);
+            }
+            files.sort();
+
+            let today = Local::now().date_naive();
+            if last_files.as_ref() != Some(&files) || last_day != Some(today) {
+                let now = Local::now();
+                let events = load(&files, now - Duration::days(DAYS_BEFORE), now + Duration::days(DAYS_AFTER));
+                if sender.send(events).is_err() {
+                    return;
+                }
+                last_files = Some(files);
+                last_day = Some(today);
+            }
+            thread::sleep(RESCAN_INTERVAL);
+        }
+    });
+    receiver
+}
+
+// Kejadian yang bersinggungan dengan tanggal `day` (waktu lokal)
+pub fn on_day(events: &[Event], day: NaiveDate) -> Vec<&Event> {
+    let start = day.and_hms_opt(0, 0, 0).and_then(|t| t.and_local_timezone(Local).earliest());
+    let end = day.succ_opt()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|t| t.and_local_timezone(Local).earliest());
+    match (start, end) {
+        (Some(start), Some(end)) => events.iter()
+            .filter(|e| e.start < end && (e.end > start || e.start >= start))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Kejadian berikutnya (bukan sepanjang hari) yang mulai dalam `within` dari sekarang
+pub fn upcoming(events: &[Event], within: Duration) -> Option<&Event> {
+    let now = Local::now();
+    events.iter().find(|e| !e.all_day && e.start > now && e.start - now <= within)
+}
@@ -0,0 +1,764 @@
+// Parser iCalendar (RFC 5545) minimal untuk agenda offline: VEVENT dengan
+// SUMMARY, DTSTART/DTEND/DURATION, RRULE sederhana (FREQ, INTERVAL, COUNT,
+// UNTIL, WKST, BYDAY mingguan), EXDATE, dan RECURRENCE-ID. VTIMEZONE tidak dibaca,
+// TZID dicocokkan langsung ke nama IANA (fallback zona lokal).
+// RRULE yang tidak didukung (BYSETPOS, "2TU", BYMONTHDAY lain, dll) hanya
+// menampilkan DTSTART, daripada menampilkan tanggal yang salah.
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+use std::collections::{HashMap, HashSet};
+
+// Batas kejadian per event dalam satu rentang (FREQ=MINUTELY setahun = ratusan ribu)
+const MAX_OCCURRENCES: usize = 20_000;
+
+#[derive(Clone)]
+pub struct Event {
+    pub summary: String,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub all_day: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Frequency {
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<DateTime<Local>>,
+    by_day: Vec<Weekday>,
+    week_start: Weekday, // WKST, menentukan batas "minggu" untuk INTERVAL > 1
+}
+
+// Satu VEVENT apa adanya, sebelum perulangan dijabarkan
+#[derive(Default)]
+struct RawEvent {
+    uid: String,
+    summary: String,
+    start: Option<(NaiveDateTime, Zone)>, // Jam dinding DTSTART di zonanya sendiri
+    end: Option<DateTime<Local>>,
+    duration: Option<Duration>,
+    all_day: bool,
+    rule: Option<String>,
+    exdates: Vec<DateTime<Local>>,
+    recurrence_id: Option<DateTime<Local>>,
+    cancelled: bool,
+}
+
+// Zona jam dinding sebuah waktu. Perulangan dijabarkan di zona DTSTART, bukan
+// zona lokal: "Senin 20:00 New York" tetap Senin walau di sini sudah Selasa,
+// dan jamnya ikut DST penyelenggara.
+#[derive(Clone, Copy, Default)]
+enum Zone {
+    #[default]
+    Local, // Floating / DATE
+    Tz(Tz), // TZID, atau UTC untuk akhiran "Z"
+}
+
+impl Zone {
+    fn localize(self, naive: &NaiveDateTime) -> Option<DateTime<Local>> {
+        match self {
+            Zone::Local => localize(&Local, naive),
+            Zone::Tz(tz) => localize(&tz, naive),
+        }
+    }
+
+    // Jam dinding di zona ini untuk waktu `time`
+    fn naive(self, time: DateTime<Local>) -> NaiveDateTime {
+        match self {
+            Zone::Local => time.naive_local(),
+            Zone::Tz(tz) => time.with_timezone(&tz).naive_local(),
+        }
+    }
+}
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+// Baris panjang dilipat jadi beberapa baris yang diawali spasi/tab
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+    lines
+}
+
+// NAME;PARAM=a;PARAM2="b:c":VALUE
+fn parse_property(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?.0;
+
+    let mut parts = line[..colon].split(';');
+    let name = parts.next()?.to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_ascii_uppercase(), v.trim_matches('"').to_string()))
+        .collect();
+    Some(Property { name, params, value: line[colon + 1..].to_string() })
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", " ").replace("\\N", " ").replace("\\,", ",").replace("\\;", ";").replace("\\\\", "\\")
+}
+
+// Waktu tanpa zona (floating / TZID) diartikan di zona `tz`
+fn localize<T: TimeZone>(tz: &T, naive: &NaiveDateTime) -> Option<DateTime<Local>> {
+    tz.from_local_datetime(naive).earliest().map(|t| t.with_timezone(&Local))
+}
+
+// Return (jam dinding, zonanya, sepanjang hari?)
+fn parse_time(value: &str, tzid: Option<&str>, date_only: bool) -> Option<(NaiveDateTime, Zone, bool)> {
+    if date_only || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_hms_opt(0, 0, 0)?, Zone::Local, true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((naive, Zone::Tz(Tz::UTC), false));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zone = match tzid.and_then(|id| id.parse::<Tz>().ok()) {
+        Some(tz) => Zone::Tz(tz),
+        None => Zone::Local,
+    };
+    Some((naive, zone, false))
+}
+
+// Waktu absolut (untuk DTEND, EXDATE, RECURRENCE-ID, UNTIL)
+fn parse_instant(value: &str, tzid: Option<&str>, date_only: bool) -> Option<DateTime<Local>> {
+    let (naive, zone, _) = parse_time(value, tzid, date_only)?;
+    zone.localize(&naive)
+}
+
+fn parse_property_instant(prop: &Property) -> Option<DateTime<Local>> {
+    parse_instant(prop.value.trim(), prop.param("TZID"), prop.param("VALUE") == Some("DATE"))
+}
+
+// DURATION: P1W, P1D, PT1H30M, P1DT12H (tanpa tanda negatif)
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.trim_start_matches('+').strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            _ => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match (c, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
+
+// Hanya hari polos ("MO"); "2TU" / "-1FR" (posisi dalam bulan) tidak didukung
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    match day {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// None = RRULE tidak didukung, pemanggil cukup menampilkan DTSTART.
+// BYMONTHDAY / BYMONTH yang sama dengan DTSTART (sering ditulis Outlook) tidak
+// mengubah hasil, jadi tetap diterima.
+fn parse_rule(value: &str, start: NaiveDateTime) -> Option<Rule> {
+    let mut rule = Rule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        week_start: Weekday::Mon,
+    };
+    let mut frequency = None;
+    for part in value.split(';') {
+        let (key, val) = part.split_once('=')?;
+        match key {
+            "FREQ" => {
+                frequency = Some(match val {
+                    "MINUTELY" => Frequency::Minutely,
+                    "HOURLY" => Frequency::Hourly,
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    _ => return None, // SECONDLY
+                })
+            }
+            "INTERVAL" => rule.interval = val.parse::<u32>().ok().filter(|i| *i > 0)?,
+            "COUNT" => rule.count = Some(val.parse::<u32>().ok()?),
+            "UNTIL" => rule.until = Some(parse_instant(val, None, false)?),
+            "WKST" => rule.week_start = parse_weekday(val)?,
+            "BYDAY" => rule.by_day = val.split(',').map(parse_weekday).collect::<Option<_>>()?,
+            "BYMONTHDAY" if val.parse::<u32>().ok() == Some(start.day()) => {}
+            "BYMONTH" if val.parse::<u32>().ok() == Some(start.month()) => {}
+            _ => return None, // BYSETPOS, BYMONTHDAY lain, BYYEARDAY, BYHOUR, dll
+        }
+    }
+    rule.frequency = frequency?;
+
+    if !rule.by_day.is_empty() {
+        match rule.frequency {
+            Frequency::Weekly => {}
+            // Tiap hari tapi hanya hari tertentu = mingguan dengan BYDAY
+            Frequency::Daily if rule.interval == 1 => rule.frequency = Frequency::Weekly,
+            _ => return None,
+        }
+    }
+    Some(rule)
+}
+
+fn parse_events(content: &str) -> Vec<RawEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+    let mut nested = 0; // VALARM dll di dalam VEVENT, propertinya bukan milik event
+
+    for line in unfold(content) {
+        let prop = match parse_property(&line) {
+            Some(p) => p,
+            None => continue,
+        };
+        match (prop.name.as_str(), prop.value.as_str()) {
+            ("BEGIN", "VEVENT") => {
+                current = Some(RawEvent::default());
+                continue;
+            }
+            ("END", "VEVENT") => {
+                events.extend(current.take());
+                continue;
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if nested > 0 => {
+                nested -= 1;
+                continue;
+            }
+            _ => {}
+        }
+        let event = match current.as_mut() {
+            Some(e) if nested == 0 => e,
+            _ => continue,
+        };
+
+        match prop.name.as_str() {
+            "UID" => event.uid = prop.value.clone(),
+            "SUMMARY" => event.summary = unescape(&prop.value),
+            "DTSTART" => {
+                let value = prop.value.trim();
+                if let Some((start, zone, all_day)) = parse_time(value, prop.param("TZID"), prop.param("VALUE") == Some("DATE")) {
+                    event.start = Some((start, zone));
+                    event.all_day = all_day;
+                }
+            }
+            "DTEND" => event.end = parse_property_instant(&prop),
+            "DURATION" => event.duration = parse_duration(&prop.value),
+            "RRULE" => event.rule = Some(prop.value.clone()),
+            "EXDATE" => {
+                for value in prop.value.split(',') {
+                    let time = parse_instant(value.trim(), prop.param("TZID"), prop.param("VALUE") == Some("DATE"));
+                    event.exdates.extend(time);
+                }
+            }
+            "RECURRENCE-ID" => event.recurrence_id = parse_property_instant(&prop),
+            "STATUS" => event.cancelled = prop.value == "CANCELLED",
+            _ => {}
+        }
+    }
+    events
+}
+
+// Pengumpul hasil perulangan: `index` = urutan kejadian sejak DTSTART (untuk COUNT)
+struct Collector {
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    until: Option<NaiveDateTime>,
+    count: Option<u64>,
+    result: Vec<NaiveDateTime>,
+}
+
+impl Collector {
+    // False kalau perulangan sudah selesai (lewat `to` / UNTIL / COUNT / batas)
+    fn offer(&mut self, index: u64, time: NaiveDateTime) -> bool {
+        if time > self.to
+            || self.until.is_some_and(|u| time > u)
+            || self.count.is_some_and(|c| index >= c)
+            || self.result.len() >= MAX_OCCURRENCES
+        {
+            return false;
+        }
+        if time >= self.from {
+            self.result.push(time);
+        }
+        true
+    }
+}
+
+// Awal tiap kejadian (jam dinding di `zone`) di rentang [from, to]. Perulangan
+// tidak dijalankan dari DTSTART: langsung lompat ke periode pertama di sekitar
+// `from`, dan urutan kejadian (COUNT) dihitung dari nomor periodenya.
+fn occurrences(start: NaiveDateTime, zone: Zone, rule: &Rule, from: DateTime<Local>, to: DateTime<Local>) -> Vec<NaiveDateTime> {
+    let (from, to) = (zone.naive(from), zone.naive(to));
+    let mut collector = Collector {
+        from,
+        to,
+        until: rule.until.map(|u| zone.naive(u)),
+        count: rule.count.map(u64::from),
+        result: Vec::new(),
+    };
+    let interval = rule.interval as i64;
+
+    match rule.frequency {
+        Frequency::Weekly if !rule.by_day.is_empty() => weekly_by_day(start, rule, &mut collector),
+        Frequency::Monthly => monthly(start, interval, &mut collector),
+        Frequency::Yearly => monthly(start, interval * 12, &mut collector),
+        _ => {
+            let step = interval * match rule.frequency {
+                Frequency::Minutely => 60,
+                Frequency::Hourly => 3600,
+                Frequency::Daily => 86_400,
+                _ => 7 * 86_400,
+            };
+            // Periode ke-k dimulai start + k*step; k pertama yang tidak sebelum `from`
+            let first = ((from - start).num_seconds().max(0) + step - 1) / step;
+            for k in first.. {
+                let time = match start.checked_add_signed(Duration::seconds(k * step)) {
+                    Some(t) => t,
+                    None => break,
+                };
+                if !collector.offer(k as u64, time) {
+                    break;
+                }
+            }
+        }
+    }
+    collector.result
+}
+
+// Mingguan dengan BYDAY: tiap periode (INTERVAL minggu, mulai dari WKST) punya
+// kejadian sebanyak hari di BYDAY, kecuali periode pertama yang dipotong DTSTART
+fn weekly_by_day(start: NaiveDateTime, rule: &Rule, collector: &mut Collector) {
+    let offset = |day: Weekday| {
+        ((day.num_days_from_monday() + 7 - rule.week_start.num_days_from_monday()) % 7) as i64
+    };
+    let mut days: Vec<i64> = rule.by_day.iter().map(|d| offset(*d)).collect();
+    days.sort();
+    days.dedup();
+
+    let week0 = start.date() - Duration::days(offset(start.weekday()));
+    let period_days = 7 * rule.interval as i64;
+    let per_period = days.len() as u64;
+    let in_first = days.iter().filter(|d| week0 + Duration::days(**d) >= start.date()).count() as u64;
+
+    let first = ((collector.from.date() - week0).num_days() / period_days).max(0);
+    for k in first.. {
+        let base = week0 + Duration::days(k * period_days);
+        let mut index = if k == 0 { 0 } else { in_first + (k as u64 - 1) * per_period };
+        for day in &days {
+            let time = (base + Duration::days(*day)).and_time(start.time());
+            if time < start {
+                continue;
+            }
+            if !collector.offer(index, time) {
+                return;
+            }
+            index += 1;
+        }
+    }
+}
+
+// Bulanan/tahunan di tanggal DTSTART. Bulan tanpa tanggal itu (31, 29 Feb)
+// dilewati dan tidak dihitung COUNT, sesuai RFC 5545.
+fn monthly(start: NaiveDateTime, months: i64, collector: &mut Collector) {
+    let first_of_month = match start.date().with_day(1) {
+        Some(d) => d,
+        None => return,
+    };
+    // Awal bulan periode ke-k, dan kejadiannya kalau tanggal DTSTART ada di bulan itu
+    let period = |k: i64| -> Option<(NaiveDate, Option<NaiveDateTime>)> {
+        let month = first_of_month.checked_add_months(Months::new(u32::try_from(k * months).ok()?))?;
+        Some((month, month.with_day(start.day()).map(|d| d.and_time(start.time()))))
+    };
+
+    let from = collector.from.date();
+    let elapsed = (from.year() - start.year()) as i64 * 12 + from.month() as i64 - start.month() as i64;
+    let first = (elapsed / months).max(0);
+    // Tanggal 1-28 ada di semua bulan, selain itu hitung bulan valid sebelum `first`
+    let mut index = if start.day() <= 28 {
+        first as u64
+    } else {
+        (0..first).filter(|k| matches!(period(*k), Some((_, Some(_))))).count() as u64
+    };
+
+    for k in first.. {
+        let (month, time) = match period(k) {
+            Some(p) => p,
+            None => return,
+        };
+        if month.and_time(start.time()) > collector.to {
+            return;
+        }
+        if let Some(time) = time {
+            if !collector.offer(index, time) {
+                return;
+            }
+            index += 1;
+        }
+    }
+}
+
+// Semua kejadian yang bersinggungan dengan rentang [from, to), perulangan sudah dijabarkan
+pub fn parse(content: &str, from: DateTime<Local>, to: DateTime<Local>) -> Vec<Event> {
+    let raw = parse_events(content);
+
+    // Kejadian yang dipindah/diubah (RECURRENCE-ID) menggantikan kejadian aslinya
+    let mut overridden: HashMap<&str, HashSet<DateTime<Local>>> = HashMap::new();
+    for event in &raw {
+        if let Some(id) = event.recurrence_id {
+            overridden.entry(event.uid.as_str()).or_default().insert(id);
+        }
+    }
+
+    let mut events = Vec::new();
+    for event in &raw {
+        let (naive_start, zone) = match event.start {
+            Some(s) => s,
+            None => continue,
+        };
+        let start = match zone.localize(&naive_start) {
+            Some(s) => s,
+            None => continue,
+        };
+        if event.cancelled {
+            continue;
+        }
+        let length = match (event.end, event.duration) {
+            (Some(end), _) if end > start => end - start,
+            (_, Some(duration)) => duration,
+            _ if event.all_day => Duration::days(1),
+            _ => Duration::zero(),
+        };
+
+        let rule = match (&event.rule, event.recurrence_id) {
+            (Some(rule), None) => parse_rule(rule, naive_start),
+            _ => None,
+        };
+        // Kejadian yang mulai sebelum `from` tapi masih berlangsung tetap ikut
+        let starts = match &rule {
+            Some(rule) => occurrences(naive_start, zone, rule, from - length, to),
+            None => vec![naive_start],
+        };
+        let skipped = overridden.get(event.uid.as_str());
+
+        for naive in starts {
+            let begin = match zone.localize(&naive) {
+                Some(b) => b,
+                None => continue,
+            };
+            if rule.is_some() && (event.exdates.contains(&begin) || skipped.is_some_and(|s| s.contains(&begin))) {
+                continue;
+            }
+            let end = begin + length;
+            if (end <= from && begin < from) || begin >= to {
+                continue;
+            }
+            events.push(Event {
+                summary: event.summary.clone(),
+                start: begin,
+                end,
+                all_day: event.all_day,
+            });
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    // Bungkus VEVENT dalam VCALENDAR dengan akhir baris CRLF seperti file aslinya
+    fn calendar(body: &str) -> String {
+        let mut content = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n");
+        for line in body.trim().lines() {
+            content.push_str(line.trim_start_matches('|'));
+            content.push_str("\r\n");
+        }
+        content.push_str("END:VCALENDAR\r\n");
+        content
+    }
+
+    fn starts(body: &str, from: DateTime<Local>, to: DateTime<Local>) -> Vec<DateTime<Local>> {
+        let mut events = parse(&calendar(body), from, to);
+        events.sort_by_key(|e| e.start);
+        events.into_iter().map(|e| e.start).collect()
+    }
+
+    #[test]
+    fn unfolds_long_lines() {
+        let body = "
+BEGIN:VEVENT
+SUMMARY:Rapat mingguan\\, tim
+| platform
+DTSTART:20250301T090000
+END:VEVENT";
+        let events = parse(&calendar(body), local(2025, 3, 1, 0, 0), local(2025, 3, 2, 0, 0));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Rapat mingguan, timplatform");
+    }
+
+    #[test]
+    fn tzid_and_utc_times() {
+        let body = "
+BEGIN:VEVENT
+SUMMARY:Jakarta
+DTSTART;TZID=Asia/Jakarta:20250301T090000
+DTEND;TZID=Asia/Jakarta:20250301T100000
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:UTC
+DTSTART:20250301T020000Z
+DURATION:PT30M
+END:VEVENT";
+        let events = parse(&calendar(body), local(2025, 2, 28, 0, 0), local(2025, 3, 3, 0, 0));
+        let jakarta = chrono_tz::Asia::Jakarta.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        assert_eq!(events.len(), 2);
+        // 09:00 WIB = 02:00 UTC
+        assert!(events.iter().all(|e| e.start == jakarta));
+        assert_eq!(events[0].end - events[0].start, Duration::hours(1));
+        assert_eq!(events[1].end - events[1].start, Duration::minutes(30));
+    }
+
+    #[test]
+    fn date_is_all_day() {
+        let body = "
+BEGIN:VEVENT
+SUMMARY:Libur
+DTSTART;VALUE=DATE:20250317
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Rapat
+DTSTART:20250317T130000
+END:VEVENT";
+        let events = parse(&calendar(body), local(2025, 3, 17, 0, 0), local(2025, 3, 18, 0, 0));
+        let holiday = events.iter().find(|e| e.summary == "Libur").unwrap();
+        assert!(holiday.all_day);
+        assert_eq!(holiday.start, local(2025, 3, 17, 0, 0));
+        assert_eq!(holiday.end, local(2025, 3, 18, 0, 0));
+        assert!(!events.iter().find(|e| e.summary == "Rapat").unwrap().all_day);
+    }
+
+    #[test]
+    fn old_daily_rule_still_expands() {
+        let body = "
+BEGIN:VEVENT
+SUMMARY:Standup
+DTSTART:20000103T090000
+RRULE:FREQ=DAILY
+END:VEVENT";
+        let found = starts(body, local(2025, 6, 10, 0, 0), local(2025, 6, 13, 0, 0));
+        assert_eq!(found, vec![local(2025, 6, 10, 9, 0), local(2025, 6, 11, 9, 0), local(2025, 6, 12, 9, 0)]);
+    }
+
+    #[test]
+    fn count_is_applied_without_expanding_from_start() {
+        let daily = "
+BEGIN:VEVENT
+DTSTART:20250101T090000
+RRULE:FREQ=DAILY;COUNT=10
+END:VEVENT";
+        // Kejadian ke-10 = 10 Januari
+        let found = starts(daily, local(2025, 1, 9, 0, 0), local(2025, 2, 1, 0, 0));
+        assert_eq!(found, vec![local(2025, 1, 9, 9, 0), local(2025, 1, 10, 9, 0)]);
+
+        let hourly = "
+BEGIN:VEVENT
+DTSTART:20250101T080000
+RRULE:FREQ=HOURLY;INTERVAL=2;COUNT=3
+END:VEVENT";
+        let found = starts(hourly, local(2025, 1, 1, 9, 0), local(2025, 1, 2, 0, 0));
+        assert_eq!(found, vec![local(2025, 1, 1, 10, 0), local(2025, 1, 1, 12, 0)]);
+
+        let minutely = "
+BEGIN:VEVENT
+DTSTART:20250101T080000
+RRULE:FREQ=MINUTELY;INTERVAL=15;UNTIL=20250101T084500
+END:VEVENT";
+        let found = starts(minutely, local(2025, 1, 1, 8, 20), local(2025, 1, 2, 0, 0));
+        assert_eq!(found, vec![local(2025, 1, 1, 8, 30), local(2025, 1, 1, 8, 45)]);
+    }
+
+    #[test]
+    fn weekly_by_day_counts_from_start() {
+        // Rabu 1 Jan: 1 (Rab), 3 (Jum), 6 (Sen), 8 (Rab), 10 (Jum) = 5 kejadian
+        let body = "
+BEGIN:VEVENT
+DTSTART:20250101T100000
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=5
+END:VEVENT";
+        let found = starts(body, local(2025, 1, 7, 0, 0), local(2025, 2, 1, 0, 0));
+        assert_eq!(found, vec![local(2025, 1, 8, 10, 0), local(2025, 1, 10, 10, 0)]);
+
+        let biweekly = "
+BEGIN:VEVENT
+DTSTART:20250106T100000
+RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH
+END:VEVENT";
+        let found = starts(biweekly, local(2025, 1, 13, 0, 0), local(2025, 1, 27, 0, 0));
+        assert_eq!(found, vec![local(2025, 1, 20, 10, 0), local(2025, 1, 23, 10, 0)]);
+    }
+
+    #[test]
+    fn rules_expand_in_the_start_zone() {
+        // Senin 20:00 New York = Selasa pagi di Asia; BYDAY tetap Senin New York,
+        // dan jamnya ikut DST New York (mulai 9 Maret 2025)
+        let body = "
+BEGIN:VEVENT
+UID:sync@finshell
+DTSTART;TZID=America/New_York:20250224T200000
+RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=4
+EXDATE;TZID=America/New_York:20250303T200000
+END:VEVENT
+BEGIN:VEVENT
+UID:sync@finshell
+RECURRENCE-ID;TZID=America/New_York:20250310T200000
+DTSTART;TZID=America/New_York:20250311T090000
+END:VEVENT";
+        let new_york = |m, d, h| chrono_tz::America::New_York.with_ymd_and_hms(2025, m, d, h, 0, 0).unwrap().with_timezone(&Local);
+        let found = starts(body, local(2025, 2, 1, 0, 0), local(2025, 4, 1, 0, 0));
+        assert_eq!(found, vec![new_york(2, 24, 20), new_york(3, 11, 9), new_york(3, 17, 20)]);
+    }
+
+    #[test]
+    fn monthly_skips_missing_days() {
+        let body = "
+BEGIN:VEVENT
+DTSTART:20250131T090000
+RRULE:FREQ=MONTHLY;COUNT=4
+END:VEVENT";
+        // Jan, Mar, Mei, Jul; Februari, April, Juni tidak punya tanggal 31
+        let found = starts(body, local(2025, 2, 1, 0, 0), local(2026, 1, 1, 0, 0));
+        assert_eq!(found, vec![local(2025, 3, 31, 9, 0), local(2025, 5, 31, 9, 0), local(2025, 7, 31, 9, 0)]);
+
+        let leap = "
+BEGIN:VEVENT
+DTSTART;VALUE=DATE:20240229
+RRULE:FREQ=YEARLY
+END:VEVENT";
+        assert!(starts(leap, local(2025, 1, 1, 0, 0), local(2028, 1, 1, 0, 0)).is_empty());
+        assert_eq!(starts(leap, local(2028, 1, 1, 0, 0), local(2029, 1, 1, 0, 0)), vec![local(2028, 2, 29, 0, 0)]);
+    }
+
+    #[test]
+    fn unsupported_rules_show_only_start() {
+        for rule in ["FREQ=MONTHLY;BYDAY=2TU", "FREQ=MONTHLY;BYMONTHDAY=15", "FREQ=MONTHLY;BYDAY=TU;BYSETPOS=-1", "FREQ=SECONDLY"] {
+            let body = format!("BEGIN:VEVENT\nDTSTART:20250114T090000\nRRULE:{rule}\nEND:VEVENT");
+            let found = starts(&body, local(2025, 1, 1, 0, 0), local(2025, 12, 31, 0, 0));
+            assert_eq!(found, vec![local(2025, 1, 14, 9, 0)], "{rule}");
+        }
+
+        // BYMONTHDAY yang sama dengan DTSTART tidak mengubah apa-apa
+        let body = "BEGIN:VEVENT\nDTSTART:20250114T090000\nRRULE:FREQ=MONTHLY;BYMONTHDAY=14\nEND:VEVENT";
+        assert_eq!(starts(body, local(2025, 1, 1, 0, 0), local(2025, 4, 1, 0, 0)).len(), 3);
+    }
+
+    #[test]
+    fn exdate_and_recurrence_id() {
+        let body = "
+BEGIN:VEVENT
+UID:rapat@finshell
+SUMMARY:Rapat
+DTSTART;TZID=Asia/Jakarta:20250106T090000
+RRULE:FREQ=DAILY;COUNT=5
+EXDATE;TZID=Asia/Jakarta:20250107T090000
+END:VEVENT
+BEGIN:VEVENT
+UID:rapat@finshell
+SUMMARY:Rapat (dipindah)
+RECURRENCE-ID;TZID=Asia/Jakarta:20250108T090000
+DTSTART;TZID=Asia/Jakarta:20250108T140000
+END:VEVENT";
+        let mut events = parse(&calendar(body), local(2025, 1, 1, 0, 0), local(2025, 2, 1, 0, 0));
+        events.sort_by_key(|e| e.start);
+        let jakarta = |d, h| chrono_tz::Asia::Jakarta.with_ymd_and_hms(2025, 1, d, h, 0, 0).unwrap().with_timezone(&Local);
+        let found: Vec<_> = events.iter().map(|e| (e.summary.as_str(), e.start)).collect();
+        assert_eq!(found, vec![
+            ("Rapat", jakarta(6, 9)),
+            ("Rapat (dipindah)", jakarta(8, 14)),
+            ("Rapat", jakarta(9, 9)),
+            ("Rapat", jakarta(10, 9)),
+        ]);
+    }
+
+    #[test]
+    fn alarms_and_cancelled_events_are_ignored() {
+        let body = "
+BEGIN:VEVENT
+SUMMARY:Dokter
+DTSTART:20250301T100000
+BEGIN:VALARM
+ACTION:DISPLAY
+SUMMARY:Pengingat
+TRIGGER:-PT15M
+END:VALARM
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Batal
+STATUS:CANCELLED
+DTSTART:20250301T120000
+END:VEVENT";
+        let events = parse(&calendar(body), local(2025, 3, 1, 0, 0), local(2025, 3, 2, 0, 0));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Dokter");
+    }
+}
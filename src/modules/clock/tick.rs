@@ -0,0 +1,120 @@
+// Jadwal update jam tepat di batas detik/menit/jam berikutnya (wall clock), bukan
+// setiap detik. Pakai timerfd CLOCK_REALTIME absolut langsung di main loop GTK:
+// - setelah suspend/resume waktu target sudah lewat, timer langsung berbunyi
+// - TFD_TIMER_CANCEL_ON_SET membangunkan kita saat jam sistem diubah (NTP, manual)
+// Setiap kali bunyi, batas berikutnya dihitung ulang dari jam sekarang.
+// Juga dipakai modul timer: on_tick return Break untuk berhenti (timer di-pause).
+use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::rc::Rc;
+
+const SECOND: i64 = 1;
+const MINUTE: i64 = 60;
+const HOUR: i64 = 3600;
+const DAY: i64 = 86400;
+// Lompatan DST terpanjang yang dicari kalau batas jatuh di jam yang tidak ada
+const GAP_SEARCH_MINS: i64 = 180;
+
+// Periode update terkecil (detik) yang dibutuhkan semua format
+pub fn resolution(formats: &[&str]) -> i64 {
+    formats.iter().map(|f| format_resolution(f)).min().unwrap_or(SECOND)
+}
+
+fn format_resolution(format: &str) -> i64 {
+    let mut resolution = DAY;
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        // Lewati flag & lebar: %-d, %_H, %3f, %.3f, %:z
+        let spec = chars.by_ref().find(|c| !matches!(c, '-' | '_' | '^' | '#' | '.' | ':' | '0'..='9'));
+        let period = match spec {
+            Some('S' | 'T' | 'X' | 'r' | 'c' | 's' | 'f' | '+') => SECOND,
+            Some('M' | 'R') => MINUTE,
+            Some('H' | 'I' | 'k' | 'l' | 'p' | 'P') => HOUR,
+            _ => DAY,
+        };
+        resolution = resolution.min(period);
+    }
+    resolution
+}
+
+// Batas berikutnya dihitung di jam dinding zona itu lalu dicari detik unix-nya,
+// bukan dengan offset saat ini: kalau DST berganti di tengah periode (jam/hari),
+// timer tetap bunyi tepat di pergantian jam/tanggal setempat. Batas juga ikut
+// zona (misal +05:30 tidak berganti jam di menit ke-30 waktu lokal).
+fn boundary_in<T: TimeZone>(tz: &T, period: i64) -> Option<i64> {
+    let now = Utc::now();
+    let wall = now.with_timezone(tz).naive_local().and_utc().timestamp();
+    let next = DateTime::from_timestamp((wall.div_euclid(period) + 1) * period, 0)?.naive_utc();
+    // Jam yang dilompati DST maju tidak ada: batasnya jam pertama yang ada sesudahnya
+    (0..=GAP_SEARCH_MINS).find_map(|minutes| {
+        let local = tz.from_local_datetime(&(next + chrono::Duration::minutes(minutes)));
+        // Jam yang terulang (DST mundur): kemunculan pertama yang belum lewat
+        [local.clone().earliest(), local.latest()].into_iter()
+            .flatten()
+            .map(|t| t.timestamp())
+            .find(|t| *t > now.timestamp())
+    })
+}
+
+// Detik unix dari batas periode berikutnya
+fn next_boundary(tz: Option<Tz>, period: i64) -> i64 {
+    let next = match tz {
+        Some(tz) => boundary_in(&tz, period),
+        None => boundary_in(&Local, period),
+    };
+    next.unwrap_or_else(|| Utc::now().timestamp() + period)
+}
+
+fn arm(fd: &OwnedFd, at: i64) -> bool {
+    let spec = libc::itimerspec {
+        it_interval: libc::timespec { tv_sec: 0, tv_nsec: 0 },
+        it_value: libc::timespec { tv_sec: at as libc::time_t, tv_nsec: 0 },
+    };
+    let flags = libc::TFD_TIMER_ABSTIME | libc::TFD_TIMER_CANCEL_ON_SET;
+    unsafe { libc::timerfd_settime(fd.as_raw_fd(), flags, &spec, std::ptr::null_mut()) == 0 }
+}
+
+// Tanpa timerfd: polling biasa (maksimal tiap menit supaya tetap cepat sinkron)
+fn poll_fallback(period: i64, on_tick: Rc<dyn Fn() -> glib::ControlFlow>) {
+    glib::timeout_add_seconds_local(period.min(MINUTE) as u32, move || on_tick());
+}
+
+pub fn start(tz: Option<Tz>, period: i64, on_tick: Rc<dyn Fn() -> glib::ControlFlow>) {
+    let raw = unsafe { libc::timerfd_create(libc::CLOCK_REALTIME, libc::TFD_NONBLOCK | libc::TFD_CLOEXEC) };
+    if raw < 0 {
+        eprintln!("[Clock] timerfd tidak tersedia, pakai polling");
+        poll_fallback(period, on_tick);
+        return;
+    }
+    let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+    if !arm(&fd, next_boundary(tz, period)) {
+        eprintln!("[Clock] Gagal memasang timerfd, pakai polling");
+        poll_fallback(period, on_tick);
+        return;
+    }
+
+    glib::unix_fd_add_local(fd.as_raw_fd(), glib::IOCondition::IN, move |_, _| {
+        // Kosongkan fd. Kalau jam sistem diubah, read() gagal dengan ECANCELED:
+        // tidak apa-apa, cukup render & pasang ulang dari jam yang baru.
+        let mut expirations = 0u64;
+        unsafe {
+            libc::read(fd.as_raw_fd(), &mut expirations as *mut u64 as *mut libc::c_void, 8);
+        }
+        if on_tick() == glib::ControlFlow::Break {
+            return glib::ControlFlow::Break;
+        }
+
+        if arm(&fd, next_boundary(tz, period)) {
+            glib::ControlFlow::Continue
+        } else {
+            // fd yang tidak dipasang ulang bisa terus "siap dibaca" -> pindah ke polling
+            eprintln!("[Clock] Gagal memasang ulang timerfd, pakai polling");
+            poll_fallback(period, on_tick.clone());
+            glib::ControlFlow::Break
+        }
+    });
+}
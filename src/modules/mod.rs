@@ -7,6 +7,7 @@ pub mod mpris;
 pub mod network;
 pub mod sys_info;
 pub mod power;
+pub mod timer;
 
 // Trait (Kontrak) yang harus dipatuhi semua widget
 pub trait WidgetModule {
@@ -1,106 +1,741 @@
 use gtk4::prelude::*;
-use gtk4::{Box, Label, Orientation, GestureClick};
+use gtk4::{Box, Button, GestureClick, Label, Orientation, Picture, Popover, ProgressBar, Scale};
 use super::WidgetModule;
+use crate::config::MprisConfig;
+use crate::text::{self, Marquee};
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::process::Command;
+use std::rc::Rc;
 use std::thread;
-use std::sync::mpsc;
-use std::time::Duration;
-use mpris::{PlayerFinder, PlaybackStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use dbus::arg::PropMap;
+use dbus::blocking::Connection;
+use dbus::blocking::stdintf::org_freedesktop_dbus::{Properties, PropertiesPropertiesChanged};
+use dbus::message::{MatchRule, SignalArgs};
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const ROOT_IFACE: &str = "org.mpris.MediaPlayer2";
+const PLAYER_IFACE: &str = "org.mpris.MediaPlayer2.Player";
+const DBUS_TIMEOUT: Duration = Duration::from_millis(500);
 
 // Data yang dikirim dari Thread ke UI
+#[derive(Clone)]
 struct MediaInfo {
     title: String,
     artist: String,
+    album: String,
+    art_url: String,
+    track_id: String,
     status: String, // "Playing", "Paused", "Stopped"
+    position_us: i64, // Posisi saat data diambil (mikrodetik)
+    length_us: i64,
+    rate: f64, // Kecepatan putar (1.5 = podcast 1.5x), untuk interpolasi posisi
+    can_seek: bool,
+    shuffle: Option<bool>,     // None = player tidak mendukung
+    loop_status: Option<String>, // "None", "Track", "Playlist"
+    player: String,      // Nama player yang sedang dikontrol (Identity)
+    player_count: usize, // Jumlah player yang bisa dipilih
+    pinned: bool,        // Player dipilih manual lewat cycle
+    has_metadata: bool,  // Player masih melaporkan lagu (walau Stopped)
     has_player: bool,
 }
 
-pub struct MprisModule;
+impl MediaInfo {
+    fn empty() -> Self {
+        MediaInfo {
+            title: String::new(),
+            artist: String::new(),
+            album: String::new(),
+            art_url: String::new(),
+            track_id: String::new(),
+            status: "Stopped".to_string(),
+            position_us: 0,
+            length_us: 0,
+            rate: 1.0,
+            can_seek: false,
+            shuffle: None,
+            loop_status: None,
+            player: String::new(),
+            player_count: 0,
+            pinned: false,
+            has_metadata: false,
+            has_player: false,
+        }
+    }
+}
+
+// Perintah dari UI ke worker kontrol
+enum MprisCommand {
+    PlayPause,
+    Previous,
+    Next,
+    Stop,
+    SetPosition(String, i64), // (track_id, posisi mikrodetik)
+    SetShuffle(bool),
+    SetLoop(String),
+    CyclePlayer,
+}
+
+// Player yang dipilih (dibagi antara listener & worker kontrol)
+#[derive(Default)]
+struct Selection {
+    target: Option<String>, // Bus name yang sedang ditampilkan
+    pinned: Option<String>, // Dipilih manual lewat cycle, menang atas aturan prioritas
+}
+
+type SharedSelection = Arc<Mutex<Selection>>;
+
+// Data terakhir + kapan diterima (untuk interpolasi posisi), hanya di GTK thread
+type SharedState = Rc<RefCell<Option<(MediaInfo, Instant)>>>;
+
+// Widget-widget di dalam popover (dibuat sekali, di-update tiap ada data baru)
+struct PopoverView {
+    popover: Popover,
+    player_btn: Button,
+    art: Picture,
+    art_url: Rc<RefCell<String>>, // Cover yang terakhir diminta; unduhan lama yang telat selesai dibuang
+    title: Label,
+    artist: Label,
+    album: Label,
+    slider: Scale,
+    dragging: Rc<Cell<bool>>, // Knob sedang ditahan mouse, tick jangan menimpa posisinya
+    elapsed: Label,
+    total: Label,
+    play_btn: Button,
+    shuffle_btn: Button,
+    loop_btn: Button,
+}
+
+pub struct MprisModule {
+    config: MprisConfig,
+}
 
 impl MprisModule {
-    // Fungsi Berat: Mencari Player dan Metadata
-    fn fetch_media_info() -> MediaInfo {
-        // PERBAIKAN DI SINI:
-        // Kita coba buat finder. Kalau gagal konek DBus, langsung return kosong.
-        let finder = match PlayerFinder::new() {
-            Ok(f) => f,
-            Err(_) => return MediaInfo {
-                title: String::new(),
-                artist: String::new(),
-                status: "Stopped".to_string(),
-                has_player: false,
-            },
-        };
-        
-        // Cari player yang aktif (Spotify, Firefox, dll)
-        // finder sekarang sudah berupa "PlayerFinder" asli, bukan "Result" lagi.
-        if let Ok(player) = finder.find_active() {
-            // Ambil Metadata
-            let title = player.get_metadata()
-                .ok()
-                .and_then(|m| m.title().map(|t| t.to_string()))
-                .unwrap_or("Unknown Title".to_string());
-
-            let artist = player.get_metadata()
-                .ok()
-                .and_then(|m| m.artists().map(|a| a.join(", ")))
-                .unwrap_or("Unknown Artist".to_string());
-
-            let status = match player.get_playback_status() {
-                Ok(PlaybackStatus::Playing) => "Playing",
-                Ok(PlaybackStatus::Paused) => "Paused",
-                _ => "Stopped",
+    pub fn new(config: MprisConfig) -> Self {
+        Self { config }
+    }
+
+    fn list_players(conn: &Connection) -> Vec<String> {
+        let proxy = conn.with_proxy("org.freedesktop.DBus", "/", DBUS_TIMEOUT);
+        let result: Result<(Vec<String>,), dbus::Error> =
+            proxy.method_call("org.freedesktop.DBus", "ListNames", ());
+
+        match result {
+            Ok((names,)) => names.into_iter().filter(|n| n.starts_with(MPRIS_PREFIX)).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn get_status(conn: &Connection, bus_name: &str) -> String {
+        conn.with_proxy(bus_name, MPRIS_PATH, DBUS_TIMEOUT)
+            .get::<String>(PLAYER_IFACE, "PlaybackStatus")
+            .unwrap_or_else(|_| "Stopped".to_string())
+    }
+
+    // Nilai metadata bisa berupa string tunggal atau array string (xesam:artist)
+    fn meta_string(meta: &PropMap, key: &str) -> Option<String> {
+        let value = &meta.get(key)?.0;
+        if let Some(s) = value.as_str() {
+            return Some(s.to_string());
+        }
+        let items: Vec<String> = value
+            .as_iter()?
+            .filter_map(|item| item.as_str().map(|s| s.to_string()))
+            .collect();
+        if items.is_empty() { None } else { Some(items.join(", ")) }
+    }
+
+    // mpris:length kadang dikirim sebagai int64, kadang uint64 (tergantung player)
+    fn meta_i64(meta: &PropMap, key: &str) -> Option<i64> {
+        let value = &meta.get(key)?.0;
+        value.as_i64().or_else(|| value.as_u64().map(|v| v as i64))
+    }
+
+    // "org.mpris.MediaPlayer2.firefox.instance_1_42" -> cocok dengan pola "firefox"
+    fn matches(bus_name: &str, pattern: &str) -> bool {
+        bus_name
+            .trim_start_matches(MPRIS_PREFIX)
+            .to_lowercase()
+            .starts_with(&pattern.to_lowercase())
+    }
+
+    fn priority_rank(bus_name: &str, config: &MprisConfig) -> usize {
+        config.priority.iter()
+            .position(|p| Self::matches(bus_name, p))
+            .unwrap_or(config.priority.len())
+    }
+
+    // Player yang boleh dikontrol, urut: Playing dulu, lalu prioritas config, lalu nama
+    fn eligible_players(conn: &Connection, config: &MprisConfig) -> Vec<(String, String)> {
+        let mut players: Vec<(String, String)> = Self::list_players(conn)
+            .into_iter()
+            .filter(|name| !config.ignore.iter().any(|p| Self::matches(name, p)))
+            .map(|name| {
+                let status = Self::get_status(conn, &name);
+                (name, status)
+            })
+            .filter(|(name, status)| {
+                status == "Playing"
+                    || !config.ignore_unless_playing.iter().any(|p| Self::matches(name, p))
+            })
+            .collect();
+
+        // Urutan harus stabil supaya widget tidak lompat-lompat antar player
+        players.sort_by_key(|(name, status)| {
+            (status != "Playing", Self::priority_rank(name, config), name.clone())
+        });
+        players
+    }
+
+    // Fungsi Berat: Pilih player lalu ambil metadata-nya
+    fn fetch_media_info(conn: &Connection, config: &MprisConfig, selection: &SharedSelection) -> MediaInfo {
+        let players = Self::eligible_players(conn, config);
+
+        let chosen = {
+            let mut sel = selection.lock().unwrap();
+            // Player pilihan manual sudah hilang / diabaikan -> kembali ke aturan prioritas
+            if sel.pinned.as_ref().is_some_and(|p| !players.iter().any(|(n, _)| n == p)) {
+                sel.pinned = None;
+            }
+            let chosen = match &sel.pinned {
+                Some(pinned) => players.iter().find(|(n, _)| n == pinned),
+                None => players.first(),
             };
+            chosen.cloned()
+        };
+
+        let pinned = selection.lock().unwrap().pinned.is_some();
+        let (bus_name, status) = match chosen {
+            Some(c) => c,
+            None => {
+                selection.lock().unwrap().target = None;
+                return MediaInfo::empty();
+            }
+        };
+
+        let proxy = conn.with_proxy(bus_name.as_str(), MPRIS_PATH, DBUS_TIMEOUT);
+        let meta = proxy.get::<PropMap>(PLAYER_IFACE, "Metadata").unwrap_or_default();
+
+        let info = MediaInfo {
+            title: Self::meta_string(&meta, "xesam:title").unwrap_or("Unknown Title".to_string()),
+            artist: Self::meta_string(&meta, "xesam:artist").unwrap_or("Unknown Artist".to_string()),
+            album: Self::meta_string(&meta, "xesam:album").unwrap_or_default(),
+            art_url: Self::meta_string(&meta, "mpris:artUrl").unwrap_or_default(),
+            track_id: Self::meta_string(&meta, "mpris:trackid").unwrap_or_default(),
+            status,
+            position_us: proxy.get::<i64>(PLAYER_IFACE, "Position").unwrap_or(0),
+            length_us: Self::meta_i64(&meta, "mpris:length").unwrap_or(0),
+            // Rate opsional di spesifikasi MPRIS; tidak ada = kecepatan normal
+            rate: proxy.get::<f64>(PLAYER_IFACE, "Rate").unwrap_or(1.0),
+            can_seek: proxy.get::<bool>(PLAYER_IFACE, "CanSeek").unwrap_or(false),
+            shuffle: proxy.get::<bool>(PLAYER_IFACE, "Shuffle").ok(),
+            loop_status: proxy.get::<String>(PLAYER_IFACE, "LoopStatus").ok(),
+            player: proxy.get::<String>(ROOT_IFACE, "Identity")
+                .unwrap_or_else(|_| bus_name.trim_start_matches(MPRIS_PREFIX).to_string()),
+            player_count: players.len(),
+            pinned,
+            has_metadata: meta.contains_key("xesam:title"),
+            has_player: true,
+        };
+
+        selection.lock().unwrap().target = Some(bus_name);
+        info
+    }
 
-            return MediaInfo {
-                title,
-                artist,
-                status: status.to_string(),
-                has_player: true,
+    // Listener: koneksi D-Bus persisten, hanya bangun saat ada sinyal
+    fn run_listener(sender: &mpsc::Sender<MediaInfo>, config: &MprisConfig, selection: &SharedSelection) -> Result<(), dbus::Error> {
+        let conn = Connection::new_session()?;
+        let dirty = Arc::new(AtomicBool::new(false));
+
+        // Metadata / PlaybackStatus / Shuffle / LoopStatus / Rate berubah
+        let dirty_props = dirty.clone();
+        let props_rule = PropertiesPropertiesChanged::match_rule(None, None)
+            .static_clone()
+            .with_path(MPRIS_PATH);
+        conn.add_match(props_rule, move |_: PropertiesPropertiesChanged, _, _| {
+            dirty_props.store(true, Ordering::Relaxed);
+            true
+        })?;
+
+        // Posisi lompat (seek dari popover atau dari aplikasi player sendiri)
+        let dirty_seek = dirty.clone();
+        let seek_rule = MatchRule::new_signal(PLAYER_IFACE, "Seeked").with_path(MPRIS_PATH);
+        conn.add_match(seek_rule, move |_: (i64,), _, _| {
+            dirty_seek.store(true, Ordering::Relaxed);
+            true
+        })?;
+
+        // Player muncul / hilang (Spotify dibuka, tab Firefox ditutup, dll)
+        let dirty_names = dirty.clone();
+        let names_rule = MatchRule::new_signal("org.freedesktop.DBus", "NameOwnerChanged");
+        conn.add_match(names_rule, move |(name, _, _): (String, String, String), _, _| {
+            if name.starts_with(MPRIS_PREFIX) {
+                dirty_names.store(true, Ordering::Relaxed);
+            }
+            true
+        })?;
+
+        // Render awal
+        if sender.send(Self::fetch_media_info(&conn, config, selection)).is_err() {
+            return Ok(());
+        }
+
+        loop {
+            // Blok sampai ada pesan masuk -> idle CPU nol
+            conn.process(Duration::from_secs(60))?;
+
+            if dirty.swap(false, Ordering::Relaxed)
+                && sender.send(Self::fetch_media_info(&conn, config, selection)).is_err() {
+                return Ok(()); // Widget sudah dihancurkan
+            }
+        }
+    }
+
+    // Worker Kontrol: satu koneksi persisten untuk semua klik
+    fn run_commands(
+        receiver: mpsc::Receiver<MprisCommand>,
+        info_sender: mpsc::Sender<MediaInfo>,
+        config: MprisConfig,
+        selection: SharedSelection,
+    ) {
+        let conn = match Connection::new_session() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("[Mpris] {}", e);
+                return;
+            }
+        };
+
+        for command in receiver {
+            // None kalau belum ada player yang dipilih
+            let proxy = selection.lock().unwrap().target.clone()
+                .map(|bus_name| conn.with_proxy(bus_name, MPRIS_PATH, DBUS_TIMEOUT));
+
+            let result: Result<(), dbus::Error> = match (command, &proxy) {
+                // Ganti player tetap jalan walau belum ada yang dipilih
+                (MprisCommand::CyclePlayer, _) => {
+                    Self::cycle_player(&conn, &info_sender, &config, &selection);
+                    continue;
+                }
+                (_, None) => continue,
+                (MprisCommand::PlayPause, Some(proxy)) => proxy.method_call(PLAYER_IFACE, "PlayPause", ()),
+                (MprisCommand::Previous, Some(proxy)) => proxy.method_call(PLAYER_IFACE, "Previous", ()),
+                (MprisCommand::Next, Some(proxy)) => proxy.method_call(PLAYER_IFACE, "Next", ()),
+                (MprisCommand::Stop, Some(proxy)) => proxy.method_call(PLAYER_IFACE, "Stop", ()),
+                (MprisCommand::SetPosition(track_id, position), Some(proxy)) => {
+                    match dbus::Path::new(track_id) {
+                        Ok(path) => proxy.method_call(PLAYER_IFACE, "SetPosition", (path, position)),
+                        Err(_) => continue, // Player tanpa trackid tidak bisa di-seek
+                    }
+                }
+                (MprisCommand::SetShuffle(value), Some(proxy)) => proxy.set(PLAYER_IFACE, "Shuffle", value),
+                (MprisCommand::SetLoop(value), Some(proxy)) => proxy.set(PLAYER_IFACE, "LoopStatus", value),
             };
+            if let Err(e) = result {
+                eprintln!("[Mpris] {}", e);
+            }
         }
+    }
 
-        // Kalau tidak ada player aktif
-        MediaInfo {
-            title: String::new(),
-            artist: String::new(),
-            status: "Stopped".to_string(),
-            has_player: false,
+    // Pindah ke player berikutnya lalu kirim datanya langsung ke UI
+    // (listener sedang tidur menunggu sinyal, jadi tidak bisa diandalkan di sini)
+    fn cycle_player(conn: &Connection, info_sender: &mpsc::Sender<MediaInfo>, config: &MprisConfig, selection: &SharedSelection) {
+        let players = Self::eligible_players(conn, config);
+        if players.len() < 2 {
+            return;
         }
+
+        {
+            let mut sel = selection.lock().unwrap();
+            let next = sel.target.as_ref()
+                .and_then(|t| players.iter().position(|(n, _)| n == t))
+                .map(|i| (i + 1) % players.len())
+                .unwrap_or(0);
+            sel.pinned = Some(players[next].0.clone());
+        }
+
+        let _ = info_sender.send(Self::fetch_media_info(conn, config, selection));
     }
 
-    fn update_view(label: &Label, container: &Box, info: MediaInfo) {
-        if !info.has_player || info.status == "Stopped" {
+    // --- ALBUM ART ---
+    // Lokasi cache untuk cover yang diunduh dari http(s)
+    fn art_cache_path(url: &str) -> Option<PathBuf> {
+        let cache_home = std::env::var("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|_| std::env::var("HOME").map(|h| PathBuf::from(h).join(".cache")))
+            .ok()?;
+
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        Some(cache_home.join("finshell/art").join(format!("{:016x}", hasher.finish())))
+    }
+
+    fn load_art(picture: &Picture, current: &Rc<RefCell<String>>, url: &str) {
+        *current.borrow_mut() = url.to_string();
+        if url.is_empty() {
+            picture.set_filename(None::<PathBuf>);
+            return;
+        }
+
+        // file:// -> langsung (gio yang urus percent-decoding)
+        if url.starts_with("file://") {
+            picture.set_file(Some(&gtk4::gio::File::for_uri(url)));
+            return;
+        }
+
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return;
+        }
+
+        let path = match Self::art_cache_path(url) {
+            Some(p) => p,
+            None => return,
+        };
+
+        if path.exists() {
+            picture.set_filename(Some(&path));
+            return;
+        }
+
+        // Belum ada di cache -> unduh di thread terpisah, UI cek hasilnya nanti.
+        // Cover lagu sebelumnya jangan tampil selama menunggu.
+        picture.set_filename(None::<PathBuf>);
+        let (sender, receiver) = mpsc::channel();
+        let download_url = url.to_string();
+        let download_path = path.clone();
+        thread::spawn(move || {
+            if let Some(dir) = download_path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let tmp_path = download_path.with_extension("part");
+            let status = Command::new("curl")
+                .args(["-sfL", "--max-time", "10", "-o"])
+                .arg(&tmp_path)
+                .arg(&download_url)
+                .status();
+            let ok = match status {
+                Ok(status) => status.success(),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    eprintln!("[Mpris] curl tidak ditemukan, cover http(s) tidak ditampilkan");
+                    false
+                }
+                Err(_) => false,
+            };
+            let ok = ok && std::fs::rename(&tmp_path, &download_path).is_ok();
+            let _ = sender.send(ok);
+        });
+
+        // Selesai unduh, track mungkin sudah ganti: pasang hanya kalau URL-nya masih sama
+        let picture_weak = picture.downgrade();
+        let current = current.clone();
+        let url = url.to_string();
+        glib::timeout_add_local(Duration::from_millis(200), move || {
+            match receiver.try_recv() {
+                Ok(ok) => {
+                    if let (true, Some(picture)) = (ok && *current.borrow() == url, picture_weak.upgrade()) {
+                        picture.set_filename(Some(&path));
+                    }
+                    glib::ControlFlow::Break
+                }
+                Err(mpsc::TryRecvError::Empty) => glib::ControlFlow::Continue,
+                Err(mpsc::TryRecvError::Disconnected) => glib::ControlFlow::Break,
+            }
+        });
+    }
+
+    fn format_time(us: i64) -> String {
+        let secs = (us / 1_000_000).max(0);
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+
+    // Posisi saat ini = posisi terakhir dari player + waktu berlalu x Rate (kalau Playing)
+    fn current_position(info: &MediaInfo, received: Instant) -> i64 {
+        let mut position = info.position_us;
+        if info.status == "Playing" {
+            position += (received.elapsed().as_micros() as f64 * info.rate) as i64;
+        }
+        if info.length_us > 0 {
+            position = position.min(info.length_us);
+        }
+        position
+    }
+
+    fn update_view(label: &Label, player_label: &Label, container: &Box, info: &MediaInfo, config: &MprisConfig, marquee: Option<&Marquee>) {
+        // Player yang di-stop tetap tampil selama masih punya lagu atau dipilih manual,
+        // supaya masih bisa di-Play lagi dari bar / popover
+        let stopped = info.status == "Stopped" && !info.has_metadata && !info.pinned;
+        if !info.has_player || stopped {
+            // Label tersembunyi tidak perlu terus digeser
+            if let Some(marquee) = marquee {
+                marquee.stop();
+            }
             container.set_visible(false);
             return;
         }
 
+        player_label.set_text(&info.player);
+        player_label.set_visible(config.show_player);
+
         container.set_visible(true);
         container.remove_css_class("paused");
-        
-        let icon = if info.status == "Playing" { 
-            "" 
-        } else { 
+
+        let icon = if info.status == "Playing" {
+            ""
+        } else {
             container.add_css_class("paused");
-            "" 
+            ""
         };
 
-        // Truncate text (batasi panjang judul)
-        let mut display_text = format!("{} - {}", info.artist, info.title);
-        if display_text.len() > 40 {
-            display_text = format!("{}...", &display_text[0..37]);
+        let prefix = format!("{}  ", icon);
+        let display_text = format!("{} - {}", info.artist, info.title);
+
+        match marquee {
+            Some(marquee) => marquee.set_text(&prefix, &display_text),
+            None => label.set_text(&format!("{}{}", prefix, text::truncate(&display_text, config.max_length))),
         }
+    }
+
+    // Waktu & progress di bar, dihitung lokal dari posisi terakhir (tanpa polling player)
+    fn update_progress(time_label: &Label, progress: &ProgressBar, info: &MediaInfo, received: Instant, config: &MprisConfig) {
+        let has_length = info.has_player && info.length_us > 0;
+        let position = Self::current_position(info, received);
 
-        label.set_text(&format!("{}  {}", icon, display_text));
+        time_label.set_visible(config.show_time && info.has_player);
+        if has_length {
+            time_label.set_text(&format!("{}/{}", Self::format_time(position), Self::format_time(info.length_us)));
+        } else {
+            time_label.set_text(&Self::format_time(position));
+        }
+
+        progress.set_visible(config.show_progress && has_length);
+        if has_length {
+            progress.set_fraction((position as f64 / info.length_us as f64).clamp(0.0, 1.0));
+        }
     }
 
-    // Fungsi Kontrol: Play/Pause
-    fn toggle_play_pause() {
-        // PERBAIKAN DI SINI JUGA:
-        // Cek dulu apakah Finder berhasil dibuat
-        if let Ok(finder) = PlayerFinder::new() {
-            if let Ok(player) = finder.find_active() {
-                let _ = player.play_pause();
+    fn update_popover(view: &PopoverView, info: &MediaInfo, received: Instant) {
+        view.player_btn.set_label(&format!("󰐹 {}", info.player));
+        view.player_btn.set_sensitive(info.player_count > 1);
+        view.title.set_text(&info.title);
+        view.artist.set_text(&info.artist);
+        view.album.set_text(&info.album);
+        view.album.set_visible(!info.album.is_empty());
+
+        view.play_btn.set_label(if info.status == "Playing" { "" } else { "" });
+
+        view.shuffle_btn.set_visible(info.shuffle.is_some());
+        if info.shuffle == Some(true) {
+            view.shuffle_btn.add_css_class("active");
+        } else {
+            view.shuffle_btn.remove_css_class("active");
+        }
+
+        view.loop_btn.set_visible(info.loop_status.is_some());
+        match info.loop_status.as_deref() {
+            Some("Track") => {
+                view.loop_btn.set_label("󰑘");
+                view.loop_btn.add_css_class("active");
+            }
+            Some("Playlist") => {
+                view.loop_btn.set_label("󰑖");
+                view.loop_btn.add_css_class("active");
+            }
+            _ => {
+                view.loop_btn.set_label("󰑗");
+                view.loop_btn.remove_css_class("active");
+            }
+        }
+
+        Self::update_slider(view, info, received);
+    }
+
+    fn update_slider(view: &PopoverView, info: &MediaInfo, received: Instant) {
+        if view.dragging.get() {
+            return;
+        }
+        let length = info.length_us.max(0) as f64 / 1_000_000.0;
+        let position = Self::current_position(info, received);
+
+        view.slider.set_sensitive(info.can_seek && length > 0.0);
+        view.slider.set_range(0.0, length.max(1.0));
+        view.slider.set_value(position as f64 / 1_000_000.0);
+
+        view.elapsed.set_text(&Self::format_time(position));
+        view.total.set_text(&Self::format_time(info.length_us));
+    }
+
+    fn build_popover(parent: &Box, cmd_sender: &mpsc::Sender<MprisCommand>, state: &SharedState) -> PopoverView {
+        let content = Box::new(Orientation::Vertical, 8);
+        content.add_css_class("mpris-popover");
+
+        // Header: player yang sedang dikontrol, klik untuk ganti
+        let player_btn = Button::with_label("");
+        player_btn.add_css_class("mpris-player-btn");
+        let s = cmd_sender.clone();
+        player_btn.connect_clicked(move |_| { let _ = s.send(MprisCommand::CyclePlayer); });
+
+        let art = Picture::new();
+        art.add_css_class("mpris-art");
+        art.set_size_request(200, 200);
+        art.set_content_fit(gtk4::ContentFit::Cover);
+
+        let title = Label::new(None);
+        title.add_css_class("mpris-title");
+        let artist = Label::new(None);
+        artist.add_css_class("mpris-artist");
+        let album = Label::new(None);
+        album.add_css_class("mpris-album");
+        for label in [&title, &artist, &album] {
+            label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+            label.set_max_width_chars(30);
+        }
+
+        // --- SEEK BAR ---
+        let slider = Scale::with_range(Orientation::Horizontal, 0.0, 1.0, 1.0);
+        slider.add_css_class("mpris-seek");
+        slider.set_draw_value(false);
+
+        let elapsed = Label::new(None);
+        elapsed.add_css_class("mpris-time");
+        let total = Label::new(None);
+        total.add_css_class("mpris-time");
+
+        let time_row = Box::new(Orientation::Horizontal, 0);
+        elapsed.set_hexpand(true);
+        elapsed.set_halign(gtk4::Align::Start);
+        total.set_halign(gtk4::Align::End);
+        time_row.append(&elapsed);
+        time_row.append(&total);
+
+        // change-value hanya terpanggil dari interaksi user (bukan set_value).
+        // Saat knob di-drag, posisi cukup diingat dan baru dikirim (sekali) saat dilepas;
+        // dari keyboard / scroll langsung dikirim.
+        let dragging = Rc::new(Cell::new(false));
+        let pending = Rc::new(Cell::new(None::<f64>));
+        let seek = {
+            let cmd_sender = cmd_sender.clone();
+            let state = state.clone();
+            move |value: f64| {
+                if let Some((info, received)) = state.borrow_mut().as_mut() {
+                    let position = (value * 1_000_000.0) as i64;
+                    let _ = cmd_sender.send(MprisCommand::SetPosition(info.track_id.clone(), position));
+                    // Anggap seek berhasil sampai sinyal Seeked datang, supaya knob tidak lompat balik
+                    info.position_us = position;
+                    *received = Instant::now();
+                }
+            }
+        };
+
+        let dragging_change = dragging.clone();
+        let pending_change = pending.clone();
+        let seek_change = seek.clone();
+        slider.connect_change_value(move |_, _, value| {
+            if dragging_change.get() {
+                pending_change.set(Some(value));
+            } else {
+                seek_change(value);
             }
+            glib::Propagation::Proceed
+        });
+
+        // Tekan/lepas dilihat di fase capture, sebelum gesture internal Scale mengambilnya
+        let press = gtk4::EventControllerLegacy::new();
+        press.set_propagation_phase(gtk4::PropagationPhase::Capture);
+        let dragging_press = dragging.clone();
+        press.connect_event(move |_, event| {
+            use gtk4::gdk::EventType;
+            match event.event_type() {
+                EventType::ButtonPress | EventType::TouchBegin => dragging_press.set(true),
+                EventType::ButtonRelease | EventType::TouchEnd | EventType::TouchCancel => {
+                    dragging_press.set(false);
+                    if let Some(value) = pending.take() {
+                        seek(value);
+                    }
+                }
+                _ => {}
+            }
+            glib::Propagation::Proceed
+        });
+        slider.add_controller(press);
+
+        // --- TOMBOL KONTROL ---
+        let controls = Box::new(Orientation::Horizontal, 5);
+        controls.set_halign(gtk4::Align::Center);
+
+        let shuffle_btn = Button::with_label("󰒟");
+        let prev_btn = Button::with_label("󰒮");
+        let play_btn = Button::with_label("");
+        let next_btn = Button::with_label("󰒭");
+        let loop_btn = Button::with_label("󰑗");
+
+        for btn in [&shuffle_btn, &prev_btn, &play_btn, &next_btn, &loop_btn] {
+            btn.add_css_class("mpris-popover-btn");
+            controls.append(btn);
+        }
+
+        let s = cmd_sender.clone();
+        prev_btn.connect_clicked(move |_| { let _ = s.send(MprisCommand::Previous); });
+        let s = cmd_sender.clone();
+        play_btn.connect_clicked(move |_| { let _ = s.send(MprisCommand::PlayPause); });
+        let s = cmd_sender.clone();
+        next_btn.connect_clicked(move |_| { let _ = s.send(MprisCommand::Next); });
+
+        let s = cmd_sender.clone();
+        let shuffle_state = state.clone();
+        shuffle_btn.connect_clicked(move |_| {
+            if let Some((info, _)) = shuffle_state.borrow().as_ref() {
+                let _ = s.send(MprisCommand::SetShuffle(!info.shuffle.unwrap_or(false)));
+            }
+        });
+
+        // Siklus: None -> Playlist -> Track -> None
+        let s = cmd_sender.clone();
+        let loop_state = state.clone();
+        loop_btn.connect_clicked(move |_| {
+            if let Some((info, _)) = loop_state.borrow().as_ref() {
+                let next = match info.loop_status.as_deref() {
+                    Some("None") => "Playlist",
+                    Some("Playlist") => "Track",
+                    _ => "None",
+                };
+                let _ = s.send(MprisCommand::SetLoop(next.to_string()));
+            }
+        });
+
+        content.append(&player_btn);
+        content.append(&art);
+        content.append(&title);
+        content.append(&artist);
+        content.append(&album);
+        content.append(&slider);
+        content.append(&time_row);
+        content.append(&controls);
+
+        let popover = Popover::new();
+        popover.set_child(Some(&content));
+        popover.set_parent(parent);
+
+        PopoverView {
+            popover,
+            player_btn,
+            art,
+            art_url: Rc::new(RefCell::new(String::new())),
+            title,
+            artist,
+            album,
+            slider,
+            dragging,
+            elapsed,
+            total,
+            play_btn,
+            shuffle_btn,
+            loop_btn,
         }
     }
 }
@@ -111,36 +746,164 @@ impl WidgetModule for MprisModule {
         container.add_css_class("mpris-widget");
         container.set_visible(false);
 
+        let config = self.config.clone();
+
+        // Nama player (klik untuk ganti player yang dikontrol)
+        let player_label = Label::new(None);
+        player_label.add_css_class("mpris-player");
+
+        let prev_btn = Button::with_label("󰒮");
+        prev_btn.add_css_class("mpris-btn");
         let label = Label::new(None);
-        container.append(&label);
+        let next_btn = Button::with_label("󰒭");
+        next_btn.add_css_class("mpris-btn");
+
+        // Waktu & progress (opsional)
+        let time_label = Label::new(None);
+        time_label.add_css_class("mpris-time-label");
+        time_label.set_visible(false);
+
+        let progress = ProgressBar::new();
+        progress.add_css_class("mpris-progress");
+        progress.set_visible(false);
+
+        // Susunan: [Judul  1:23/3:45]
+        //          [=====-----------]
+        let title_row = Box::new(Orientation::Horizontal, 5);
+        title_row.append(&label);
+        title_row.append(&time_label);
+
+        let text_box = Box::new(Orientation::Vertical, 0);
+        text_box.set_valign(gtk4::Align::Center);
+        text_box.append(&title_row);
+        text_box.append(&progress);
+
+        container.append(&player_label);
+        container.append(&prev_btn);
+        container.append(&text_box);
+        container.append(&next_btn);
+
+        let selection: SharedSelection = Arc::new(Mutex::new(Selection::default()));
+        let state: SharedState = Rc::new(RefCell::new(None));
+        let (sender, receiver) = mpsc::channel();
+
+        // --- WORKER KONTROL ---
+        let (cmd_sender, cmd_receiver) = mpsc::channel();
+        let info_sender = sender.clone();
+        let config_cmd = config.clone();
+        let selection_cmd = selection.clone();
+        thread::spawn(move || {
+            Self::run_commands(cmd_receiver, info_sender, config_cmd, selection_cmd);
+        });
+
+        let view = Rc::new(Self::build_popover(&container, &cmd_sender, &state));
+
+        let s = cmd_sender.clone();
+        prev_btn.connect_clicked(move |_| { let _ = s.send(MprisCommand::Previous); });
+        let s = cmd_sender.clone();
+        next_btn.connect_clicked(move |_| { let _ = s.send(MprisCommand::Next); });
 
         // --- INTERAKSI KLIK ---
+        // Kiri: play/pause, Tengah: stop, Kanan: popover
         let click = GestureClick::new();
-        click.connect_pressed(move |_, _, _, _| {
-            thread::spawn(|| {
-                Self::toggle_play_pause();
-            });
+        click.set_button(0); // Terima semua tombol mouse
+        let s = cmd_sender.clone();
+        let view_click = view.clone();
+        click.connect_pressed(move |gesture, _, _, _| {
+            match gesture.current_button() {
+                1 => { let _ = s.send(MprisCommand::PlayPause); }
+                2 => { let _ = s.send(MprisCommand::Stop); }
+                3 => view_click.popover.popup(),
+                _ => {}
+            }
+        });
+        label.add_controller(click);
+
+        let cycle_click = GestureClick::new();
+        let s = cmd_sender.clone();
+        cycle_click.connect_pressed(move |_, _, _, _| {
+            let _ = s.send(MprisCommand::CyclePlayer);
+        });
+        player_label.add_controller(cycle_click);
+
+        // --- SCROLL: ganti lagu ---
+        let scroll = gtk4::EventControllerScroll::new(gtk4::EventControllerScrollFlags::VERTICAL);
+        // Dipasang di teks judul (bukan container) supaya scroll di dalam popover
+        // tidak ikut naik dan mengganti lagu
+        let s = cmd_sender.clone();
+        scroll.connect_scroll(move |_, _, dy| {
+            if dy < 0.0 { let _ = s.send(MprisCommand::Next); }
+            else if dy > 0.0 { let _ = s.send(MprisCommand::Previous); }
+            glib::Propagation::Stop
+        });
+        text_box.add_controller(scroll);
+
+        // Judul panjang: teks berjalan (opsional) atau dipotong "..."
+        let marquee = config.marquee.then(|| {
+            Marquee::new(&label, config.max_length, Duration::from_millis(config.marquee_interval_ms))
         });
-        container.add_controller(click);
 
         // --- THREADING LOGIC ---
-        let (sender, receiver) = mpsc::channel();
         let container_weak = container.downgrade();
 
-        // Worker Thread
+        // Worker Thread: reconnect kalau session bus putus
+        let config_listener = config.clone();
         thread::spawn(move || {
             loop {
-                let info = Self::fetch_media_info();
-                let _ = sender.send(info);
-                thread::sleep(Duration::from_secs(1));
+                match Self::run_listener(&sender, &config_listener, &selection) {
+                    Ok(()) => break,
+                    Err(e) => eprintln!("[Mpris] {}", e),
+                }
+                if sender.send(MediaInfo::empty()).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_secs(2));
             }
         });
 
         // UI Update
+        let view_update = view.clone();
+        let state_update = state.clone();
+        let time_label_tick = time_label.clone();
+        let progress_tick = progress.clone();
+        let config_tick = config.clone();
         glib::timeout_add_local(Duration::from_millis(100), move || {
             if let Some(container) = container_weak.upgrade() {
-                if let Ok(info) = receiver.try_recv() {
-                    Self::update_view(&label, &container, info);
+                if let Some(info) = receiver.try_iter().last() {
+                    let received = Instant::now();
+                    Self::update_view(&label, &player_label, &container, &info, &config, marquee.as_ref());
+
+                    // Cover hanya dimuat ulang kalau URL-nya berubah
+                    let art_changed = state_update.borrow().as_ref()
+                        .map(|(old, _)| old.art_url != info.art_url)
+                        .unwrap_or(true);
+                    if art_changed {
+                        Self::load_art(&view_update.art, &view_update.art_url, &info.art_url);
+                    }
+
+                    Self::update_popover(&view_update, &info, received);
+                    Self::update_progress(&time_label, &progress, &info, received, &config);
+                    if !info.has_player {
+                        view_update.popover.popdown();
+                    }
+                    *state_update.borrow_mut() = Some((info, received));
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+
+        // Seek bar & progress jalan sendiri (interpolasi), posisi asli hanya
+        // diambil ulang saat ada sinyal Seeked / PropertiesChanged
+        let view_tick = view.clone();
+        glib::timeout_add_local(Duration::from_millis(500), move || {
+            if let Some((info, received)) = state.borrow().as_ref() {
+                if info.status == "Playing" {
+                    if view_tick.popover.is_visible() {
+                        Self::update_slider(&view_tick, info, *received);
+                    }
+                    if config_tick.show_time || config_tick.show_progress {
+                        Self::update_progress(&time_label_tick, &progress_tick, info, *received, &config_tick);
+                    }
                 }
             }
             glib::ControlFlow::Continue
@@ -148,4 +911,4 @@ impl WidgetModule for MprisModule {
 
         container.upcast()
     }
-}
\ No newline at end of file
+}
@@ -1,126 +1,373 @@
 use gtk4::prelude::*;
 use gtk4::{Box, Label, Orientation, GestureClick};
 use super::WidgetModule;
+use crate::config::NetworkConfig;
+use crate::popover;
+use crate::text;
+use connectivity::Checker;
+use picker::WifiPicker;
+use throughput::Throughput;
+use std::cell::{Cell, RefCell};
 use std::process::Command;
+use std::rc::Rc;
 use std::thread;
 use std::sync::mpsc;
 use std::time::Duration;
 use std::fs;
+use std::path::Path;
 
+mod connectivity;
+mod details;
+mod nl80211;
+mod nm;
+mod picker;
+mod throughput;
+
+// Jenis link, dari tipe koneksi NM atau tebakan sysfs
+#[derive(Clone, Copy, PartialEq)]
+enum LinkKind {
+    Wifi,
+    Ethernet,
+    Tether, // USB/Bluetooth tethering, modem GSM
+    Bridge,
+    Vpn,
+    WireGuard,
+    Other,
+}
+
+impl LinkKind {
+    fn is_tunnel(self) -> bool {
+        matches!(self, LinkKind::Vpn | LinkKind::WireGuard)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LinkKind::Wifi => "WiFi",
+            LinkKind::Ethernet => "Ethernet",
+            LinkKind::Tether => "Tether",
+            LinkKind::Bridge => "Bridge",
+            LinkKind::Vpn => "VPN",
+            LinkKind::WireGuard => "WireGuard",
+            LinkKind::Other => "Link",
+        }
+    }
+}
+
+// Status internet di atas link yang sudah up
+#[derive(Clone, Copy, PartialEq)]
+enum Connectivity {
+    Unknown, // Belum dicek / backend tidak mendukung
+    NoInternet,
+    Portal, // Captive portal (butuh login di browser)
+    Limited,
+    Full,
+}
+
+impl Connectivity {
+    // Nilai properti Connectivity NetworkManager (NMConnectivityState)
+    fn from_nm(value: u32) -> Self {
+        match value {
+            1 => Connectivity::NoInternet,
+            2 => Connectivity::Portal,
+            3 => Connectivity::Limited,
+            4 => Connectivity::Full,
+            _ => Connectivity::Unknown,
+        }
+    }
+
+    fn describe(self) -> Option<&'static str> {
+        match self {
+            Connectivity::Unknown => None,
+            Connectivity::NoInternet => Some("Internet: tidak ada"),
+            Connectivity::Portal => Some("Internet: captive portal (klik untuk login)"),
+            Connectivity::Limited => Some("Internet: terbatas"),
+            Connectivity::Full => Some("Internet: tersambung"),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Link {
+    interface: String,
+    kind: LinkKind,
+    name: String,        // SSID / nama koneksi NM / nama interface
+    signal_strength: u8, // Hanya untuk WiFi
+    // Detail untuk tooltip (lihat details.rs)
+    ipv4: Vec<String>,      // CIDR, misal "192.168.1.5/24"
+    ipv6: Vec<String>,
+    gateways: Vec<String>,
+    dbm: Option<i32>,       // Level sinyal mentah WiFi
+    frequency: Option<u32>, // MHz
+    bitrate: Option<u32>,   // Mbit/s
+}
+
+impl Link {
+    fn new(interface: String, kind: LinkKind, name: String) -> Self {
+        Self {
+            interface,
+            kind,
+            name,
+            signal_strength: 0,
+            ipv4: Vec::new(),
+            ipv6: Vec::new(),
+            gateways: Vec::new(),
+            dbm: None,
+            frequency: None,
+            bitrate: None,
+        }
+    }
+
+    // Baris tooltip untuk satu link
+    fn describe(&self) -> String {
+        let mut lines = vec![format!("{}  {}  {}", self.interface, self.kind.label(), self.name)];
+
+        for addr in &self.ipv4 {
+            lines.push(format!("    IPv4: {}", addr));
+        }
+        for addr in &self.ipv6 {
+            lines.push(format!("    IPv6: {}", addr));
+        }
+        if !self.gateways.is_empty() {
+            lines.push(format!("    Gateway: {}", self.gateways.join(", ")));
+        }
+
+        // Baris radio WiFi: band, bitrate, dBm (yang tersedia saja)
+        let mut radio = Vec::new();
+        if let Some(freq) = self.frequency {
+            radio.push(format!("{} ({} MHz)", details::band(freq), freq));
+        }
+        if let Some(rate) = self.bitrate {
+            radio.push(format!("{} Mbit/s", rate));
+        }
+        if let Some(dbm) = self.dbm {
+            radio.push(format!("{} dBm", dbm));
+        }
+        if !radio.is_empty() {
+            lines.push(format!("    {}", radio.join(" · ")));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[derive(Clone)]
 struct NetworkInfo {
+    // Ringkasan dari link utama (yang tampil di bar)
     connected: bool,
     is_wifi: bool,
     ssid: String,
     signal_strength: u8,
+    links: Vec<Link>, // Semua link aktif, link utama paling depan
+    vpn_active: bool,
+    dns: Vec<String>,
+    connectivity: Connectivity,
+    portal_url: Option<String>, // Tujuan redirect portal (hanya dari cek lokal)
+    rx_rate: f64, // byte/detik
+    tx_rate: f64,
+    rx_total: u64, // byte sejak boot
+    tx_total: u64,
 }
 
-pub struct NetworkModule;
+impl NetworkInfo {
+    fn offline() -> Self {
+        NetworkInfo {
+            connected: false,
+            is_wifi: false,
+            ssid: "Offline".to_string(),
+            signal_strength: 0,
+            links: Vec::new(),
+            vpn_active: false,
+            dns: Vec::new(),
+            connectivity: Connectivity::Unknown,
+            portal_url: None,
+            rx_rate: 0.0,
+            tx_rate: 0.0,
+            rx_total: 0,
+            tx_total: 0,
+        }
+    }
 
-impl NetworkModule {
-    // Fungsi khusus membaca sinyal langsung dari kernel Linux
-    // Ini jauh lebih cepat & akurat daripada spawn nmcli
-    fn get_wifi_signal() -> u8 {
-        if let Ok(content) = fs::read_to_string("/proc/net/wireless") {
-            for line in content.lines() {
-                // Format: wlan0: 0000  70.  -40. ...
-                // Kolom ke-3 biasanya adalah Link Quality (biasanya max 70 atau 100)
-                if line.contains(":") {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() > 3 {
-                        // Ambil angka, buang titik di belakangnya (misal "70.")
-                        let raw_signal = parts[2].trim_matches('.');
-                        let signal_val = raw_signal.parse::<f32>().unwrap_or(0.0);
-                        
-                        // Konversi ke persen (asumsi max 70 di file wireless standar)
-                        let percent = if signal_val > 70.0 {
-                            signal_val // Kalau formatnya sudah persen (0-100)
-                        } else {
-                            (signal_val / 70.0) * 100.0 // Konversi 0-70 ke 0-100
-                        };
-                        return percent as u8;
-                    }
-                }
+    // Pola sederhana dengan wildcard '*', misal "docker*", "br-*", "*tun*"
+    fn matches_pattern(name: &str, pattern: &str) -> bool {
+        let parts: Vec<&str> = pattern.split('*').collect();
+        if parts.len() == 1 {
+            return name == pattern;
+        }
+
+        let (first, last) = (parts[0], parts[parts.len() - 1]);
+        if !name.starts_with(first) || name.len() < first.len() + last.len() || !name.ends_with(last) {
+            return false;
+        }
+
+        let mut rest = &name[first.len()..name.len() - last.len()];
+        for part in &parts[1..parts.len() - 1] {
+            match rest.find(part) {
+                Some(i) => rest = &rest[i + part.len()..],
+                None => return false,
             }
         }
-        0
-    }
-
-    fn fetch_network_info() -> NetworkInfo {
-        // STRATEGI BARU: Tanya "Connection Active", bukan "Device"
-        // Output contoh: 
-        // 802-11-wireless:Infinix 1:wlan0
-        // 802-3-ethernet:Wired connection 1:enp3s0
-        let output = Command::new("nmcli")
-            .env("LC_ALL", "C")
-            .args(["-t", "-f", "TYPE,NAME", "connection", "show", "--active"])
-            .output();
-
-        if let Ok(out) = output {
-            let string_out = String::from_utf8_lossy(&out.stdout);
-
-            for line in string_out.lines() {
-                let parts: Vec<&str> = line.splitn(2, ':').collect();
-                if parts.len() < 2 { continue; }
-
-                let conn_type = parts[0];
-                let conn_name = parts[1]; // INI PASTI NAMA SSID (Infinix 1)
-
-                // Cek WiFi
-                if conn_type == "802-11-wireless" || conn_type == "wifi" {
-                    return NetworkInfo {
-                        connected: true,
-                        is_wifi: true,
-                        ssid: conn_name.to_string(),
-                        signal_strength: Self::get_wifi_signal(), // Ambil dari kernel
-                    };
-                }
-                
-                // Cek Ethernet
-                if conn_type == "802-3-ethernet" || conn_type == "ethernet" {
-                    return NetworkInfo {
-                        connected: true,
-                        is_wifi: false,
-                        ssid: conn_name.to_string(),
-                        signal_strength: 0,
-                    };
-                }
+        true
+    }
+
+    fn interface_allowed(name: &str, config: &NetworkConfig) -> bool {
+        let included = config.include_interfaces.is_empty()
+            || config.include_interfaces.iter().any(|p| Self::matches_pattern(name, p));
+        let excluded = config.exclude_interfaces.iter().any(|p| Self::matches_pattern(name, p));
+        included && !excluded
+    }
+
+    // Terapkan filter interface, lalu isi ringkasan dari link utama.
+    // Link utama = link non-tunnel pertama (backend menaruh koneksi default paling depan),
+    // dan selalu jadi elemen pertama `links` setelah fungsi ini.
+    fn finalize(&mut self, config: &NetworkConfig) {
+        self.links.retain(|link| Self::interface_allowed(&link.interface, config));
+        self.vpn_active = self.links.iter().any(|link| link.kind.is_tunnel());
+
+        // PrimaryConnection NM bisa berupa VPN, jadi link fisik dipindah ke depan
+        if let Some(i) = self.links.iter().position(|link| !link.kind.is_tunnel() && link.kind != LinkKind::Bridge) {
+            let link = self.links.remove(i);
+            self.links.insert(0, link);
+        }
+
+        match self.links.first().cloned() {
+            Some(link) => {
+                self.connected = true;
+                self.is_wifi = link.kind == LinkKind::Wifi;
+                self.ssid = link.name;
+                self.signal_strength = link.signal_strength;
+            }
+            None => {
+                self.connected = false;
+                self.is_wifi = false;
+                self.ssid = "Offline".to_string();
+                self.signal_strength = 0;
+            }
+        }
+    }
+}
+
+pub struct NetworkModule {
+    config: NetworkConfig,
+}
+
+impl NetworkModule {
+    pub fn new(config: NetworkConfig) -> Self {
+        Self { config }
+    }
+
+    // --- FALLBACK (Jika NetworkManager tidak berjalan) ---
+    // Tebak jenis link dari sysfs (folder wireless, DEVTYPE, driver tethering)
+    fn sysfs_kind(name: &str) -> LinkKind {
+        let base = Path::new("/sys/class/net").join(name);
+        if base.join("wireless").exists() || base.join("phy80211").exists() {
+            return LinkKind::Wifi;
+        }
+
+        let uevent = fs::read_to_string(base.join("uevent")).unwrap_or_default();
+        if uevent.lines().any(|l| l == "DEVTYPE=wireguard") {
+            return LinkKind::WireGuard;
+        }
+        if uevent.lines().any(|l| l == "DEVTYPE=bridge") {
+            return LinkKind::Bridge;
+        }
+
+        // type 65534 = ARPHRD_NONE (tun, dipakai OpenVPN dkk)
+        if fs::read_to_string(base.join("type")).map(|t| t.trim() == "65534").unwrap_or(false) {
+            return LinkKind::Vpn;
+        }
+
+        let driver = fs::read_link(base.join("device/driver"))
+            .ok()
+            .and_then(|p| p.file_name().map(|f| f.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        if ["rndis_host", "cdc_ether", "cdc_ncm", "ipheth"].contains(&driver.as_str()) {
+            return LinkKind::Tether;
+        }
+
+        LinkKind::Ethernet
+    }
+
+    // Interface tunnel sering berstatus "unknown" walau aktif, jadi cek carrier juga
+    fn sysfs_is_up(name: &str) -> bool {
+        let base = Path::new("/sys/class/net").join(name);
+        match fs::read_to_string(base.join("operstate")).map(|s| s.trim().to_string()) {
+            Ok(state) if state == "up" => true,
+            Ok(state) if state == "unknown" => {
+                fs::read_to_string(base.join("carrier")).map(|c| c.trim() == "1").unwrap_or(false)
             }
+            _ => false,
         }
+    }
+
+    // Cek folder /sys/class/net
+    fn fetch_sysfs_info() -> NetworkInfo {
+        let mut links = Vec::new();
 
-        // --- FALLBACK (Jika nmcli gagal total) ---
-        // Kembali ke cara cek folder /sys/class/net
         if let Ok(entries) = fs::read_dir("/sys/class/net") {
             for entry in entries.flatten() {
                 let name = entry.file_name().to_string_lossy().to_string();
-                if name == "lo" || name.starts_with("docker") || name.starts_with("veth") { continue; }
-
-                let operstate_path = entry.path().join("operstate");
-                if let Ok(state) = fs::read_to_string(operstate_path) {
-                    if state.trim() == "up" {
-                        let is_wifi = name.starts_with("w") || name.starts_with("wl");
-                        return NetworkInfo {
-                            connected: true,
-                            is_wifi,
-                            ssid: name, // Terpaksa pakai nama interface (wlan0)
-                            signal_strength: if is_wifi { Self::get_wifi_signal() } else { 0 },
-                        };
-                    }
-                }
+                if !Self::sysfs_is_up(&name) { continue; }
+
+                let kind = Self::sysfs_kind(&name);
+                // Terpaksa pakai nama interface (wlan0) sebagai nama link.
+                // Sinyal WiFi diisi details::collect via nl80211.
+                links.push(Link::new(name.clone(), kind, name));
             }
         }
 
+        // Urutan read_dir acak, taruh link fisik di depan supaya stabil
+        links.sort_by_key(|link| (link.kind.is_tunnel(), link.interface.clone()));
+
         NetworkInfo {
-            connected: false,
-            is_wifi: false,
-            ssid: "Offline".to_string(),
-            signal_strength: 0,
+            links,
+            ..NetworkInfo::offline()
         }
     }
 
+    // Tooltip: detail semua link aktif, DNS (+ total trafik kalau throughput aktif)
+    fn tooltip_text(info: &NetworkInfo, show_rate: bool) -> String {
+        let mut lines: Vec<String> = info.links.iter().map(Link::describe).collect();
+        if lines.is_empty() {
+            lines.push("Offline".to_string());
+        }
+        if !info.dns.is_empty() {
+            lines.push(format!("DNS: {}", info.dns.join(", ")));
+        }
+        if let (true, Some(state)) = (info.connected, info.connectivity.describe()) {
+            lines.push(state.to_string());
+        }
+
+        if show_rate {
+            lines.push(String::new());
+            lines.push(format!(
+                "Total sejak boot: 󰇚 {}  󰕒 {}",
+                throughput::format_bytes(info.rx_total as f64),
+                throughput::format_bytes(info.tx_total as f64),
+            ));
+        }
+        lines.join("\n")
+    }
+
+    fn update_rate(rate_label: &Label, info: &NetworkInfo) {
+        rate_label.set_text(&format!(
+            "󰇚 {}/s 󰕒 {}/s",
+            throughput::format_bytes(info.rx_rate),
+            throughput::format_bytes(info.tx_rate),
+        ));
+    }
+
     fn update_view(label: &Label, container: &Box, info: NetworkInfo) {
-        container.remove_css_class("disconnected");
-        container.remove_css_class("wifi");
-        container.remove_css_class("ethernet");
+        for class in ["disconnected", "wifi", "ethernet", "tether", "vpn", "portal", "limited", "no-internet"] {
+            container.remove_css_class(class);
+        }
+
+        // Indikator tunnel VPN / WireGuard di depan label
+        let vpn_prefix = if info.vpn_active {
+            container.add_css_class("vpn");
+            "󰦝 "
+        } else {
+            ""
+        };
 
         if !info.connected {
             label.set_text("󰤮 Offline");
@@ -128,6 +375,25 @@ impl NetworkModule {
             return;
         }
 
+        // Link up tapi internet bermasalah: ikon peringatan di depan label
+        let prefix = match info.connectivity {
+            Connectivity::Portal => {
+                container.add_css_class("portal");
+                format!("{}󰖟 ", vpn_prefix)
+            }
+            Connectivity::Limited => {
+                container.add_css_class("limited");
+                format!("{}󰀦 ", vpn_prefix)
+            }
+            Connectivity::NoInternet => {
+                container.add_css_class("no-internet");
+                format!("{}󰀦 ", vpn_prefix)
+            }
+            _ => vpn_prefix.to_string(),
+        };
+
+        let primary_kind = info.links.first().map(|l| l.kind).unwrap_or(LinkKind::Ethernet);
+
         if info.is_wifi {
             container.add_css_class("wifi");
             
@@ -139,24 +405,21 @@ impl NetworkModule {
                 _        => "󰤯",
             };
             
-            // Truncate SSID
-            let mut ssid = info.ssid.clone();
-            if ssid.len() > 15 {
-                ssid = format!("{}...", &ssid[0..12]);
-            }
+            // Truncate SSID (per grapheme, aman untuk SSID non-ASCII)
+            let ssid = text::truncate(&info.ssid, 15);
 
-            label.set_text(&format!("{} {}", icon, ssid));
+            label.set_text(&format!("{}{} {}", prefix, icon, ssid));
+        } else if primary_kind == LinkKind::Tether {
+            container.add_css_class("tether");
+            label.set_text(&format!("{}󰕓 Tether", prefix));
+        } else if primary_kind.is_tunnel() {
+            // Hanya tunnel yang lolos filter (misal link fisik di-exclude)
+            label.set_text(&format!("{}{}", prefix, text::truncate(&info.ssid, 15)));
         } else {
             container.add_css_class("ethernet");
-            label.set_text("󰈀 Wired");
+            label.set_text(&format!("{}󰈀 Wired", prefix));
         }
     }
-
-    fn open_manager() {
-        let _ = Command::new("nm-connection-editor").spawn().or_else(|_| {
-             Command::new("kitty").arg("-e").arg("nmtui").spawn()
-        });
-    }
 }
 
 impl WidgetModule for NetworkModule {
@@ -167,24 +430,115 @@ impl WidgetModule for NetworkModule {
         let label = Label::new(None);
         container.append(&label);
 
+        // Kecepatan upload/download (opsional)
+        let rate_label = Label::new(None);
+        rate_label.add_css_class("network-rate");
+        rate_label.set_visible(self.config.throughput);
+        container.append(&rate_label);
+
+        // URL yang dibuka saat klik, hanya terisi ketika status captive portal.
+        // Dibuka sekali per masuk status portal; klik berikutnya membuka popover WiFi.
+        let portal: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+        let portal_opened = Rc::new(Cell::new(false));
+
+        // Klik kiri -> popover pemilih WiFi (tanpa nm-connection-editor / terminal),
+        // atau halaman login kalau sedang di belakang captive portal.
+        // Klik kanan -> selalu popover WiFi.
+        let picker = WifiPicker::new(&container);
         let click = GestureClick::new();
-        click.connect_pressed(|_, _, _, _| { Self::open_manager(); });
+        click.set_button(0);
+        let portal_click = portal.clone();
+        let portal_opened_click = portal_opened.clone();
+        click.connect_pressed(move |gesture, _, _, _| {
+            if popover::from_popover(gesture) {
+                return;
+            }
+            let button = gesture.current_button();
+            if button == 1 && !portal_opened_click.get() {
+                if let Some(url) = portal_click.borrow().as_ref() {
+                    let _ = Command::new("xdg-open").arg(url).spawn();
+                    portal_opened_click.set(true);
+                    return;
+                }
+            }
+            if button == 1 || button == 3 {
+                picker.open();
+            }
+        });
         container.add_controller(click);
 
         let (sender, receiver) = mpsc::channel();
         let container_weak = container.downgrade();
 
+        let config = self.config.clone();
+        let show_rate = config.throughput;
+        let portal_default = config.portal_url.clone();
+
         thread::spawn(move || {
+            let mut warned = false;
+            let interval = Duration::from_secs(config.throughput_interval_secs.max(1));
+            let throughput_tick = config.throughput.then_some(interval);
+
+            let mut meter = Throughput::new(config.throughput_interface.clone());
+            let mut checker = Checker::new(&config, sender.clone());
+
+            // Cek lokal butuh dibangunkan berkala walau NM diam
+            let check_tick = checker.as_ref().map(|_| Duration::from_secs(config.connectivity_interval_secs.max(5)));
+            let tick = match (throughput_tick, check_tick) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+
+            let mut enrich = |info: &mut NetworkInfo| {
+                details::collect(info);
+                info.finalize(&config);
+                if let Some(checker) = checker.as_mut() {
+                    checker.apply(info);
+                }
+                if config.throughput {
+                    meter.sample(info);
+                }
+            };
+
             loop {
-                let info = Self::fetch_network_info();
-                let _ = sender.send(info);
-                thread::sleep(Duration::from_secs(5));
+                // Utama: NetworkManager D-Bus (event-driven, tanpa spawn proses)
+                match nm::run(&sender, tick, &mut enrich) {
+                    Ok(()) => break,
+                    Err(e) => {
+                        if !warned {
+                            eprintln!("[Network] NetworkManager tidak tersedia, pakai sysfs: {}", e);
+                            warned = true;
+                        }
+                    }
+                }
+
+                // Fallback sysfs (polling), coba konek NM lagi tiap ~30 detik
+                let poll = throughput_tick.unwrap_or(Duration::from_secs(5));
+                let rounds = (30 / poll.as_secs().max(1)).max(1);
+                for _ in 0..rounds {
+                    let mut info = Self::fetch_sysfs_info();
+                    enrich(&mut info);
+                    if sender.send(info).is_err() {
+                        return;
+                    }
+                    thread::sleep(poll);
+                }
             }
         });
 
         glib::timeout_add_local(Duration::from_millis(100), move || {
             if let Some(container) = container_weak.upgrade() {
-                if let Ok(info) = receiver.try_recv() {
+                if let Some(info) = receiver.try_iter().last() {
+                    let current = (info.connected && info.connectivity == Connectivity::Portal)
+                        .then(|| info.portal_url.clone().unwrap_or_else(|| portal_default.clone()));
+                    if *portal.borrow() != current {
+                        portal_opened.set(false);
+                        *portal.borrow_mut() = current;
+                    }
+                    if show_rate {
+                        Self::update_rate(&rate_label, &info);
+                    }
+                    container.set_tooltip_text(Some(&Self::tooltip_text(&info, show_rate)));
                     Self::update_view(&label, &container, info);
                 }
             }
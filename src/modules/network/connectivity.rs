@@ -0,0 +1,195 @@
+// Cek koneksi internet lokal (pengganti properti Connectivity NetworkManager).
+// Request HTTP ke URL yang dikonfigurasi; redirect / isi yang tidak sesuai
+// berarti ada captive portal di depan.
+// Request jalan di thread sendiri (bisa sampai beberapa detik), supaya worker NM
+// tidak berhenti memproses sinyal. Worker hanya membaca hasil terakhir, dan thread
+// cek mengirim ulang info terakhir begitu hasil baru keluar.
+use super::{Connectivity, NetworkInfo};
+use crate::config::NetworkConfig;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+// Balasan halaman cek biasanya kecil; sisanya tidak perlu dibaca
+const MAX_RESPONSE: u64 = 64 * 1024;
+
+// (link utama saat dicek, hasil, URL portal)
+type Probe = (String, Connectivity, Option<String>);
+
+// URL http://host[:port]/path. Cek captive portal memang harus HTTP biasa:
+// portal tidak bisa membelokkan HTTPS.
+struct Target {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Target {
+    fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix("http://")?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().ok()?),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return None;
+        }
+        Some(Self { host: host.to_string(), port, path: path.to_string() })
+    }
+}
+
+pub struct Checker {
+    interval: Duration,
+    requests: mpsc::Sender<String>,
+    // Kapan terakhir minta cek, untuk link mana
+    requested: Option<(Instant, String)>,
+    result: Arc<Mutex<Option<Probe>>>,
+    // Info terakhir dari worker NM, dikirim ulang thread cek dengan hasil baru
+    latest: Arc<Mutex<Option<NetworkInfo>>>,
+}
+
+fn link_key(info: &NetworkInfo) -> String {
+    info.links.first()
+        .map(|link| format!("{}/{}", link.interface, link.name))
+        .unwrap_or_default()
+}
+
+impl Checker {
+    // None kalau cek lokal tidak dikonfigurasi (pakai NetworkManager saja)
+    pub fn new(config: &NetworkConfig, sender: mpsc::Sender<NetworkInfo>) -> Option<Self> {
+        let url = config.connectivity_url.as_ref()?;
+        let target = match Target::parse(url) {
+            Some(t) => t,
+            None => {
+                eprintln!("[Network] connectivity_url '{}' harus berupa http://..., pakai NetworkManager", url);
+                return None;
+            }
+        };
+        let expected = config.connectivity_response.clone();
+
+        let (requests, receiver) = mpsc::channel::<String>();
+        let result = Arc::new(Mutex::new(None));
+        let latest: Arc<Mutex<Option<NetworkInfo>>> = Arc::new(Mutex::new(None));
+        let (result_probe, latest_probe) = (result.clone(), latest.clone());
+
+        thread::spawn(move || {
+            while let Ok(key) = receiver.recv() {
+                // Permintaan yang menumpuk selama cek sebelumnya cukup dicek sekali
+                let key = receiver.try_iter().last().unwrap_or(key);
+                let (state, portal) = probe(&target, expected.as_deref());
+                if let Ok(mut result) = result_probe.lock() {
+                    *result = Some((key.clone(), state, portal.clone()));
+                }
+
+                let info = latest_probe.lock().ok().and_then(|latest| latest.clone());
+                if let Some(mut info) = info.filter(|info| info.connected && link_key(info) == key) {
+                    info.connectivity = state;
+                    info.portal_url = portal;
+                    if sender.send(info).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Some(Self {
+            interval: Duration::from_secs(config.connectivity_interval_secs.max(5)),
+            requests,
+            requested: None,
+            result,
+            latest,
+        })
+    }
+
+    // Minta cek ulang kalau sudah lewat interval atau link utama berganti;
+    // info diisi hasil terakhir untuk link yang sama (kalau belum ada, nilai NM tetap)
+    pub fn apply(&mut self, info: &mut NetworkInfo) {
+        if !info.connected {
+            info.connectivity = Connectivity::NoInternet;
+            self.requested = None;
+            if let Ok(mut result) = self.result.lock() {
+                *result = None;
+            }
+        } else {
+            let key = link_key(info);
+            let stale = match &self.requested {
+                Some((time, requested)) => time.elapsed() >= self.interval || *requested != key,
+                None => true,
+            };
+            if stale {
+                let _ = self.requests.send(key.clone());
+                self.requested = Some((Instant::now(), key.clone()));
+            }
+
+            if let Some((checked, state, portal)) = self.result.lock().ok().and_then(|r| r.clone()) {
+                if checked == key {
+                    info.connectivity = state;
+                    info.portal_url = portal;
+                }
+            }
+        }
+
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = Some(info.clone());
+        }
+    }
+}
+
+// Balasan mentah (header + body), None kalau tidak ada balasan sama sekali
+// (DNS gagal, timeout, dll). HTTP/1.0 supaya body tidak di-chunk.
+fn request(target: &Target) -> Option<String> {
+    let addr = (target.host.as_str(), target.port).to_socket_addrs().ok()?.next()?;
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(TIMEOUT)).ok()?;
+    let request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: finshell\r\nConnection: close\r\n\r\n",
+        target.path, target.host
+    );
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = Vec::new();
+    let _ = stream.take(MAX_RESPONSE).read_to_end(&mut response);
+    (!response.is_empty()).then(|| String::from_utf8_lossy(&response).into_owned())
+}
+
+fn probe(target: &Target, expected: Option<&str>) -> (Connectivity, Option<String>) {
+    let response = match request(target) {
+        Some(r) => r,
+        None => return (Connectivity::NoInternet, None),
+    };
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let mut lines = head.lines();
+    let code: u16 = lines.next()
+        .and_then(|status| status.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .unwrap_or(0);
+    let location = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("location").then(|| value.trim().to_string())
+    });
+
+    match code {
+        204 => (Connectivity::Full, None),
+        200 => match expected {
+            Some(expected) if !body.contains(expected) => (Connectivity::Portal, None),
+            _ => (Connectivity::Full, None),
+        },
+        300..=399 => {
+            // Location relatif ("/login") dilengkapi host URL cek
+            let portal = location.filter(|l| !l.is_empty()).map(|l| {
+                if l.starts_with('/') { format!("http://{}{}", target.host, l) } else { l }
+            });
+            (Connectivity::Portal, portal)
+        }
+        _ => (Connectivity::Limited, None),
+    }
+}
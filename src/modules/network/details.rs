@@ -0,0 +1,188 @@
+// Detail link untuk tooltip: alamat IP, gateway, DNS, dan info radio WiFi.
+// Semua dibaca langsung dari kernel (getifaddrs, /proc/net/*, nl80211), tanpa spawn `ip`/`iw`.
+use super::nl80211;
+use super::{LinkKind, NetworkInfo};
+use std::ffi::CStr;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+// Lengkapi semua link di `info` (dipanggil di worker thread sebelum finalize,
+// supaya kekuatan sinyal link utama ikut terbawa ke ringkasan)
+pub fn collect(info: &mut NetworkInfo) {
+    let addresses = read_addresses();
+    let gateways = read_gateways();
+
+    for link in &mut info.links {
+        let own = addresses.iter().filter(|(name, _, _)| *name == link.interface);
+        link.ipv4 = own.clone()
+            .filter(|(_, addr, _)| addr.is_ipv4())
+            .map(|(_, addr, prefix)| format!("{}/{}", addr, prefix))
+            .collect();
+        link.ipv6 = own
+            .filter(|(_, addr, _)| addr.is_ipv6())
+            .map(|(_, addr, prefix)| format!("{}/{}", addr, prefix))
+            .collect();
+        link.gateways = gateways.iter()
+            .filter(|(name, _)| *name == link.interface)
+            .map(|(_, gw)| gw.clone())
+            .collect();
+
+        if link.kind != LinkKind::Wifi || link.interface.is_empty() {
+            continue;
+        }
+
+        // Sinyal per interface dari nl80211; /proc/net/wireless hanya cadangan
+        if let Some(station) = nl80211::station(&link.interface) {
+            link.dbm = Some(station.signal_dbm);
+            link.signal_strength = nl80211::quality(station.signal_dbm);
+            link.bitrate = station.bitrate.or(link.bitrate);
+            link.frequency = link.frequency.or(station.frequency);
+        } else if let Some(dbm) = nl80211::proc_level(&link.interface) {
+            link.dbm = Some(dbm);
+            link.signal_strength = nl80211::quality(dbm);
+        }
+    }
+
+    info.dns = read_dns();
+}
+
+// "2.4 GHz" / "5 GHz" / "6 GHz" dari frekuensi channel (MHz)
+pub fn band(frequency: u32) -> &'static str {
+    match frequency {
+        2400..=2500 => "2.4 GHz",
+        4900..=5900 => "5 GHz",
+        5925..=7125 => "6 GHz",
+        _ => "?",
+    }
+}
+
+// Alamat per interface + panjang prefix (untuk format CIDR "192.168.1.5/24").
+// IPv6 link-local (fe80::/10) dilewati karena selalu ada dan jarang berguna.
+fn read_addresses() -> Vec<(String, IpAddr, u32)> {
+    let mut result = Vec::new();
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+
+    // SAFETY: daftar dari getifaddrs hanya dibaca sampai freeifaddrs,
+    // dan setiap sockaddr di-cast sesuai sa_family-nya.
+    unsafe {
+        if libc::getifaddrs(&mut ifap) != 0 {
+            return result;
+        }
+
+        let mut cursor = ifap;
+        while !cursor.is_null() {
+            let ifa = &*cursor;
+            cursor = ifa.ifa_next;
+            if ifa.ifa_addr.is_null() {
+                continue;
+            }
+
+            let name = CStr::from_ptr(ifa.ifa_name).to_string_lossy().to_string();
+            let (addr, prefix) = match i32::from((*ifa.ifa_addr).sa_family) {
+                libc::AF_INET => {
+                    let sin = &*(ifa.ifa_addr as *const libc::sockaddr_in);
+                    let prefix = if ifa.ifa_netmask.is_null() {
+                        32
+                    } else {
+                        let mask = &*(ifa.ifa_netmask as *const libc::sockaddr_in);
+                        mask.sin_addr.s_addr.count_ones()
+                    };
+                    (IpAddr::V4(Ipv4Addr::from(sin.sin_addr.s_addr.to_ne_bytes())), prefix)
+                }
+                libc::AF_INET6 => {
+                    let sin6 = &*(ifa.ifa_addr as *const libc::sockaddr_in6);
+                    let addr = Ipv6Addr::from(sin6.sin6_addr.s6_addr);
+                    if addr.segments()[0] & 0xffc0 == 0xfe80 {
+                        continue;
+                    }
+                    let prefix = if ifa.ifa_netmask.is_null() {
+                        128
+                    } else {
+                        let mask = &*(ifa.ifa_netmask as *const libc::sockaddr_in6);
+                        mask.sin6_addr.s6_addr.iter().map(|b| b.count_ones()).sum()
+                    };
+                    (IpAddr::V6(addr), prefix)
+                }
+                _ => continue,
+            };
+
+            result.push((name, addr, prefix));
+        }
+
+        libc::freeifaddrs(ifap);
+    }
+
+    result
+}
+
+// Default gateway per interface dari tabel routing kernel (IPv4 & IPv6)
+fn read_gateways() -> Vec<(String, String)> {
+    let mut result = Vec::new();
+
+    // Format: Iface Destination Gateway Flags RefCnt Use Metric Mask ...
+    // Nilai hex berisi byte network-order yang dicetak sebagai u32 native
+    if let Ok(content) = fs::read_to_string("/proc/net/route") {
+        for line in content.lines().skip(1) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 8 || parts[1] != "00000000" || parts[7] != "00000000" {
+                continue;
+            }
+            if let Ok(raw) = u32::from_str_radix(parts[2], 16) {
+                if raw != 0 {
+                    result.push((parts[0].to_string(), Ipv4Addr::from(raw.to_ne_bytes()).to_string()));
+                }
+            }
+        }
+    }
+
+    // Format: dest dest_len src src_len next_hop metric refcnt use flags iface
+    if let Ok(content) = fs::read_to_string("/proc/net/ipv6_route") {
+        for line in content.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 10 || parts[1] != "00" || parts[0].chars().any(|c| c != '0') {
+                continue;
+            }
+            if let Some(addr) = parse_ipv6_hex(parts[4]) {
+                if !addr.is_unspecified() {
+                    result.push((parts[9].to_string(), addr.to_string()));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn parse_ipv6_hex(hex: &str) -> Option<Ipv6Addr> {
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(Ipv6Addr::from(bytes))
+}
+
+// Server DNS dari resolv.conf. Kalau isinya hanya stub systemd-resolved
+// (127.0.0.53), ambil daftar upstream yang sebenarnya.
+fn read_dns() -> Vec<String> {
+    let parse = |path: &str| -> Vec<String> {
+        fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("nameserver"))
+            .map(|server| server.trim().to_string())
+            .filter(|server| !server.is_empty())
+            .collect()
+    };
+
+    let servers = parse("/etc/resolv.conf");
+    if servers.iter().all(|server| server == "127.0.0.53") {
+        let upstream = parse("/run/systemd/resolve/resolv.conf");
+        if !upstream.is_empty() {
+            return upstream;
+        }
+    }
+    servers
+}
@@ -0,0 +1,545 @@
+// Backend NetworkManager via D-Bus (system bus).
+// Tidak ada spawn proses: state diambil dari properti NM dan
+// di-refresh hanya saat NM mengirim sinyal.
+use super::{Connectivity, Link, LinkKind, NetworkInfo};
+use dbus::arg::{RefArg, Variant};
+use dbus::blocking::Connection;
+use dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+use dbus::channel::MatchingReceiver;
+use dbus::message::MatchRule;
+use dbus::Path;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const NM_BUS: &str = "org.freedesktop.NetworkManager";
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+const NM_IFACE: &str = "org.freedesktop.NetworkManager";
+const ACTIVE_IFACE: &str = "org.freedesktop.NetworkManager.Connection.Active";
+const DEVICE_IFACE: &str = "org.freedesktop.NetworkManager.Device";
+const WIRELESS_IFACE: &str = "org.freedesktop.NetworkManager.Device.Wireless";
+const AP_IFACE: &str = "org.freedesktop.NetworkManager.AccessPoint";
+const AP_PATH_PREFIX: &str = "/org/freedesktop/NetworkManager/AccessPoint/";
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+// Hasil fetch + path access point aktif (untuk menyaring sinyal Strength)
+struct Snapshot {
+    info: NetworkInfo,
+    active_ap: String,
+}
+
+fn get_active_connections(conn: &Connection) -> Result<Vec<Path<'static>>, dbus::Error> {
+    conn.with_proxy(NM_BUS, NM_PATH, TIMEOUT)
+        .get::<Vec<Path<'static>>>(NM_IFACE, "ActiveConnections")
+}
+
+// Detail access point yang sedang dipakai device WiFi
+struct WifiDetails {
+    ssid: String,
+    strength: u8,
+    frequency: u32, // MHz
+    bitrate: u32,   // Mbit/s
+    ap_path: String,
+}
+
+fn get_wifi_details(conn: &Connection, device: &Path) -> Option<WifiDetails> {
+    let device_proxy = conn.with_proxy(NM_BUS, device.clone(), TIMEOUT);
+    let ap: Path<'static> = device_proxy.get(WIRELESS_IFACE, "ActiveAccessPoint").ok()?;
+    if &*ap == "/" {
+        return None;
+    }
+
+    let ap_proxy = conn.with_proxy(NM_BUS, ap.clone(), TIMEOUT);
+    let ssid: Vec<u8> = ap_proxy.get(AP_IFACE, "Ssid").ok()?;
+    let strength: u8 = ap_proxy.get(AP_IFACE, "Strength").unwrap_or(0);
+    let frequency: u32 = ap_proxy.get(AP_IFACE, "Frequency").unwrap_or(0);
+    // Bitrate dari NM dalam Kbit/s
+    let bitrate: u32 = device_proxy.get::<u32>(WIRELESS_IFACE, "Bitrate").unwrap_or(0) / 1000;
+
+    Some(WifiDetails {
+        ssid: String::from_utf8_lossy(&ssid).to_string(),
+        strength,
+        frequency,
+        bitrate,
+        ap_path: ap.to_string(),
+    })
+}
+
+// Tipe koneksi NM -> jenis link
+fn link_kind(conn_type: &str) -> LinkKind {
+    match conn_type {
+        "802-11-wireless" => LinkKind::Wifi,
+        "802-3-ethernet" => LinkKind::Ethernet,
+        "vpn" => LinkKind::Vpn,
+        "wireguard" => LinkKind::WireGuard,
+        "bridge" => LinkKind::Bridge,
+        "gsm" | "cdma" | "bluetooth" => LinkKind::Tether,
+        _ => LinkKind::Other,
+    }
+}
+
+// Nama interface: IpInterface (ppp0, tun0) lebih akurat dari Interface (ttyUSB0)
+fn device_interface(conn: &Connection, device: &Path) -> String {
+    let proxy = conn.with_proxy(NM_BUS, device.clone(), TIMEOUT);
+    proxy.get::<String>(DEVICE_IFACE, "IpInterface")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .or_else(|| proxy.get::<String>(DEVICE_IFACE, "Interface").ok())
+        .unwrap_or_default()
+}
+
+fn fetch(conn: &Connection) -> Result<Snapshot, dbus::Error> {
+    let mut actives = get_active_connections(conn)?;
+    let nm_proxy = conn.with_proxy(NM_BUS, NM_PATH, TIMEOUT);
+
+    // Hasil cek internet NM (termasuk deteksi captive portal)
+    let connectivity: u32 = nm_proxy.get(NM_IFACE, "Connectivity").unwrap_or(0);
+
+    // Koneksi default route (PrimaryConnection) ditaruh paling depan
+    let primary: Path<'static> = nm_proxy
+        .get(NM_IFACE, "PrimaryConnection")
+        .unwrap_or_else(|_| Path::from("/"));
+    if let Some(i) = actives.iter().position(|p| *p == primary) {
+        let path = actives.remove(i);
+        actives.insert(0, path);
+    }
+
+    let mut links = Vec::new();
+    let mut active_ap = String::new();
+
+    for active in actives {
+        let proxy = conn.with_proxy(NM_BUS, active.clone(), TIMEOUT);
+        let conn_type: String = proxy.get(ACTIVE_IFACE, "Type").unwrap_or_default();
+        let name: String = proxy.get(ACTIVE_IFACE, "Id").unwrap_or_default();
+        // Loopback ikut terdaftar sebagai koneksi aktif di NM versi baru
+        if conn_type == "loopback" {
+            continue;
+        }
+
+        let devices: Vec<Path<'static>> = proxy.get(ACTIVE_IFACE, "Devices").unwrap_or_default();
+        let kind = link_kind(&conn_type);
+        let interface = devices.first()
+            .map(|device| device_interface(conn, device))
+            .unwrap_or_default();
+
+        let mut link = Link::new(interface, kind, name);
+
+        if kind == LinkKind::Wifi {
+            if let Some(wifi) = devices.first().and_then(|d| get_wifi_details(conn, d)) {
+                link.name = wifi.ssid;
+                link.signal_strength = wifi.strength;
+                link.frequency = (wifi.frequency > 0).then_some(wifi.frequency);
+                link.bitrate = (wifi.bitrate > 0).then_some(wifi.bitrate);
+                // Sinyal Strength hanya dipantau untuk AP milik link WiFi pertama
+                if active_ap.is_empty() {
+                    active_ap = wifi.ap_path;
+                }
+            }
+        }
+
+        links.push(link);
+    }
+
+    Ok(Snapshot {
+        info: NetworkInfo {
+            links,
+            connectivity: Connectivity::from_nm(connectivity),
+            ..NetworkInfo::offline()
+        },
+        active_ap,
+    })
+}
+
+// Listener: blok menunggu sinyal NM. Return Err kalau NM tidak ada / bus putus,
+// supaya pemanggil bisa pindah ke fallback sysfs.
+// `tick` (opsional) membangunkan loop secara berkala untuk data yang tidak
+// punya sinyal (misal throughput), `enrich` melengkapi info sebelum dikirim.
+pub fn run(
+    sender: &mpsc::Sender<NetworkInfo>,
+    tick: Option<Duration>,
+    enrich: &mut dyn FnMut(&mut NetworkInfo),
+) -> Result<(), dbus::Error> {
+    let conn = Connection::new_system()?;
+
+    // Fetch pertama sekaligus cek apakah NetworkManager berjalan
+    let snapshot = fetch(&conn)?;
+    let active_ap = Arc::new(Mutex::new(snapshot.active_ap));
+    let mut last_info = snapshot.info;
+
+    let mut info = last_info.clone();
+    enrich(&mut info);
+    if sender.send(info).is_err() {
+        return Ok(());
+    }
+
+    let dirty = Arc::new(AtomicBool::new(false));
+
+    // StateChanged: connect / disconnect / connecting
+    let state_rule = MatchRule::new_signal(NM_IFACE, "StateChanged").with_sender(NM_BUS);
+    // PropertiesChanged: koneksi aktif berganti, Strength access point berubah, dll
+    let props_rule = MatchRule::new_signal("org.freedesktop.DBus.Properties", "PropertiesChanged")
+        .with_sender(NM_BUS);
+
+    for rule in [state_rule, props_rule] {
+        conn.add_match_no_cb(&rule.match_str())?;
+
+        let dirty = dirty.clone();
+        let active_ap = active_ap.clone();
+        conn.start_receive(rule, Box::new(move |msg: dbus::Message, _: &Connection| {
+            // Hasil scan mengubah Strength semua AP di sekitar, yang penting hanya AP aktif
+            let path = msg.path().map(|p| p.to_string()).unwrap_or_default();
+            if path.starts_with(AP_PATH_PREFIX) && path != *active_ap.lock().unwrap() {
+                return true;
+            }
+            dirty.store(true, Ordering::Relaxed);
+            true
+        }));
+    }
+
+    let mut next_tick = tick.map(|t| Instant::now() + t);
+
+    loop {
+        // Blok sampai NM mengirim sinyal (atau sampai jadwal tick berikutnya)
+        let wait = next_tick
+            .map(|t| t.saturating_duration_since(Instant::now()))
+            .unwrap_or(Duration::from_secs(60));
+        conn.process(wait)?;
+
+        let changed = dirty.swap(false, Ordering::Relaxed);
+        let ticked = next_tick.is_some_and(|t| Instant::now() >= t);
+        if !changed && !ticked {
+            continue;
+        }
+
+        if changed {
+            let snapshot = fetch(&conn)?;
+            *active_ap.lock().unwrap() = snapshot.active_ap;
+            last_info = snapshot.info;
+        }
+        if let (true, Some(t)) = (ticked, tick) {
+            next_tick = Some(Instant::now() + t);
+        }
+
+        let mut info = last_info.clone();
+        enrich(&mut info);
+        if sender.send(info).is_err() {
+            return Ok(()); // Widget sudah dihancurkan
+        }
+    }
+}
+
+// --- WIFI PICKER ---
+const SETTINGS_PATH: &str = "/org/freedesktop/NetworkManager/Settings";
+const SETTINGS_IFACE: &str = "org.freedesktop.NetworkManager.Settings";
+const SETTINGS_CONN_IFACE: &str = "org.freedesktop.NetworkManager.Settings.Connection";
+const DEVICE_TYPE_WIFI: u32 = 2;
+
+// Flag keamanan AP (NM80211ApSecurityFlags / NM80211ApFlags)
+const AP_FLAGS_PRIVACY: u32 = 0x1;
+const AP_SEC_KEY_MGMT_PSK: u32 = 0x100;
+const AP_SEC_KEY_MGMT_802_1X: u32 = 0x200;
+const AP_SEC_KEY_MGMT_SAE: u32 = 0x400;
+const AP_SEC_KEY_MGMT_OWE: u32 = 0x800;
+const AP_SEC_KEY_MGMT_OWE_TM: u32 = 0x1000;
+const AP_SEC_KEY_MGMT_EAP_SUITE_B_192: u32 = 0x2000;
+
+// NMActiveConnectionState
+const ACTIVE_STATE_ACTIVATED: u32 = 2;
+const ACTIVE_STATE_DEACTIVATED: u32 = 4;
+
+// Scan & aktivasi bisa lama, jangan pakai TIMEOUT pendek
+const SLOW_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Cara autentikasi AP, menentukan isi setting 802-11-wireless-security
+#[derive(Clone, Copy, PartialEq)]
+pub enum KeyMgmt {
+    Open,
+    Wep,
+    Psk,        // WPA/WPA2 personal (juga WPA3 transisi)
+    Sae,        // WPA3 personal murni
+    Owe,        // Enhanced Open: terenkripsi tapi tanpa password
+    Enterprise, // 802.1X / EAP, butuh sertifikat & identitas
+}
+
+impl KeyMgmt {
+    fn from_flags(flags: u32, wpa_flags: u32, rsn_flags: u32) -> Self {
+        let both = wpa_flags | rsn_flags;
+        if both & (AP_SEC_KEY_MGMT_802_1X | AP_SEC_KEY_MGMT_EAP_SUITE_B_192) != 0 {
+            KeyMgmt::Enterprise
+        } else if both & AP_SEC_KEY_MGMT_PSK != 0 {
+            KeyMgmt::Psk
+        } else if rsn_flags & AP_SEC_KEY_MGMT_SAE != 0 {
+            KeyMgmt::Sae
+        } else if rsn_flags & (AP_SEC_KEY_MGMT_OWE | AP_SEC_KEY_MGMT_OWE_TM) != 0 {
+            KeyMgmt::Owe
+        } else if both != 0 {
+            KeyMgmt::Psk
+        } else if flags & AP_FLAGS_PRIVACY != 0 {
+            KeyMgmt::Wep
+        } else {
+            KeyMgmt::Open
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessPoint {
+    pub path: String,
+    pub ssid: String,
+    pub strength: u8,
+    pub security: String, // "", "WEP", "WPA", "WPA2", "WPA3", "OWE", "802.1X"
+    pub key_mgmt: KeyMgmt,
+    pub active: bool,
+    pub saved: bool,
+}
+
+impl AccessPoint {
+    pub fn is_secured(&self) -> bool {
+        self.key_mgmt != KeyMgmt::Open
+    }
+
+    pub fn needs_password(&self) -> bool {
+        matches!(self.key_mgmt, KeyMgmt::Wep | KeyMgmt::Psk | KeyMgmt::Sae)
+    }
+}
+
+fn wifi_device(conn: &Connection) -> Result<Option<Path<'static>>, dbus::Error> {
+    let (devices,): (Vec<Path<'static>>,) = conn.with_proxy(NM_BUS, NM_PATH, TIMEOUT)
+        .method_call(NM_IFACE, "GetDevices", ())?;
+
+    Ok(devices.into_iter().find(|device| {
+        conn.with_proxy(NM_BUS, device.clone(), TIMEOUT)
+            .get::<u32>(DEVICE_IFACE, "DeviceType")
+            .map(|t| t == DEVICE_TYPE_WIFI)
+            .unwrap_or(false)
+    }))
+}
+
+fn security_label(flags: u32, wpa_flags: u32, rsn_flags: u32) -> String {
+    let label = match KeyMgmt::from_flags(flags, wpa_flags, rsn_flags) {
+        KeyMgmt::Enterprise => "802.1X",
+        KeyMgmt::Owe => "OWE",
+        KeyMgmt::Wep => "WEP",
+        KeyMgmt::Open => "",
+        _ if rsn_flags & AP_SEC_KEY_MGMT_SAE != 0 => "WPA3",
+        _ if rsn_flags != 0 => "WPA2",
+        _ => "WPA",
+    };
+    label.to_string()
+}
+
+// SSID dari semua koneksi WiFi yang sudah tersimpan (tidak perlu minta password lagi)
+fn saved_ssids(conn: &Connection) -> Vec<String> {
+    let settings = conn.with_proxy(NM_BUS, SETTINGS_PATH, TIMEOUT);
+    let connections: Vec<Path<'static>> = match settings.method_call(SETTINGS_IFACE, "ListConnections", ()) {
+        Ok((list,)) => list,
+        Err(_) => return Vec::new(),
+    };
+
+    connections
+        .into_iter()
+        .filter_map(|path| {
+            let (config,): (HashMap<String, dbus::arg::PropMap>,) = conn
+                .with_proxy(NM_BUS, path, TIMEOUT)
+                .method_call(SETTINGS_CONN_IFACE, "GetSettings", ())
+                .ok()?;
+            let ssid = &config.get("802-11-wireless")?.get("ssid")?.0;
+            let bytes: Vec<u8> = ssid.as_iter()?.filter_map(|b| b.as_u64().map(|b| b as u8)).collect();
+            Some(String::from_utf8_lossy(&bytes).to_string())
+        })
+        .collect()
+}
+
+// Daftar AP hasil scan terakhir + status radio WiFi
+pub fn list_access_points(conn: &Connection) -> Result<(Vec<AccessPoint>, bool), dbus::Error> {
+    let enabled: bool = conn.with_proxy(NM_BUS, NM_PATH, TIMEOUT).get(NM_IFACE, "WirelessEnabled")?;
+    let device = match wifi_device(conn)? {
+        Some(d) => d,
+        None => return Ok((Vec::new(), enabled)),
+    };
+
+    let device_proxy = conn.with_proxy(NM_BUS, device, TIMEOUT);
+    let active_ap: Path<'static> = device_proxy.get(WIRELESS_IFACE, "ActiveAccessPoint")
+        .unwrap_or_else(|_| Path::from("/"));
+    let (paths,): (Vec<Path<'static>>,) = device_proxy.method_call(WIRELESS_IFACE, "GetAllAccessPoints", ())?;
+
+    let saved = saved_ssids(conn);
+    let mut aps: Vec<AccessPoint> = Vec::new();
+
+    for path in paths {
+        let proxy = conn.with_proxy(NM_BUS, path.clone(), TIMEOUT);
+        let ssid: Vec<u8> = match proxy.get(AP_IFACE, "Ssid") {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        let ssid = String::from_utf8_lossy(&ssid).to_string();
+        if ssid.is_empty() {
+            continue; // Hidden network
+        }
+
+        let flags: u32 = proxy.get(AP_IFACE, "Flags").unwrap_or(0);
+        let wpa_flags: u32 = proxy.get(AP_IFACE, "WpaFlags").unwrap_or(0);
+        let rsn_flags: u32 = proxy.get(AP_IFACE, "RsnFlags").unwrap_or(0);
+        let ap = AccessPoint {
+            path: path.to_string(),
+            strength: proxy.get(AP_IFACE, "Strength").unwrap_or(0),
+            security: security_label(flags, wpa_flags, rsn_flags),
+            key_mgmt: KeyMgmt::from_flags(flags, wpa_flags, rsn_flags),
+            active: path == active_ap,
+            saved: saved.contains(&ssid),
+            ssid,
+        };
+
+        // Satu SSID bisa dipancarkan banyak AP (mesh / 2.4 + 5 GHz), ambil yang terkuat
+        match aps.iter_mut().find(|a| a.ssid == ap.ssid) {
+            Some(existing) => {
+                if ap.active || (!existing.active && ap.strength > existing.strength) {
+                    *existing = ap;
+                }
+            }
+            None => aps.push(ap),
+        }
+    }
+
+    aps.sort_by_key(|a| (!a.active, std::cmp::Reverse(a.strength)));
+    Ok((aps, enabled))
+}
+
+pub fn request_scan(conn: &Connection) -> Result<(), dbus::Error> {
+    if let Some(device) = wifi_device(conn)? {
+        let options: dbus::arg::PropMap = HashMap::new();
+        conn.with_proxy(NM_BUS, device, SLOW_TIMEOUT)
+            .method_call::<(), _, _, _>(WIRELESS_IFACE, "RequestScan", (options,))?;
+    }
+    Ok(())
+}
+
+// Aktivasi yang diterima NM tapi belum tentu berhasil (password salah baru
+// ketahuan belakangan). `created` = koneksi baru yang dibuat untuk aktivasi ini.
+pub struct Activation {
+    pub active: Path<'static>,
+    pub device: Path<'static>,
+    pub created: Option<Path<'static>>,
+}
+
+pub enum ActivationState {
+    Pending,
+    Activated,
+    Failed(String),
+}
+
+type SettingsSection = HashMap<&'static str, Variant<Box<dyn RefArg>>>;
+
+// Setting 802-11-wireless-security untuk koneksi baru, sesuai jenis AP
+fn security_settings(key_mgmt: KeyMgmt, password: Option<String>) -> Option<SettingsSection> {
+    let mut security = SettingsSection::new();
+    let mut set = |key: &'static str, value: Box<dyn RefArg>| {
+        security.insert(key, Variant(value));
+    };
+
+    match key_mgmt {
+        KeyMgmt::Open | KeyMgmt::Enterprise => return None,
+        KeyMgmt::Owe => set("key-mgmt", Box::new("owe".to_string())),
+        KeyMgmt::Wep => {
+            let key = password?;
+            // Key langsung (5/13 ASCII atau 10/26 hex) = tipe 1, selain itu passphrase = tipe 2
+            let key_type: u32 = if matches!(key.len(), 5 | 10 | 13 | 26) { 1 } else { 2 };
+            set("key-mgmt", Box::new("none".to_string()));
+            set("wep-key-type", Box::new(key_type));
+            set("wep-key0", Box::new(key));
+        }
+        KeyMgmt::Psk | KeyMgmt::Sae => {
+            let name = if key_mgmt == KeyMgmt::Sae { "sae" } else { "wpa-psk" };
+            set("key-mgmt", Box::new(name.to_string()));
+            set("psk", Box::new(password?));
+        }
+    }
+    Some(security)
+}
+
+// Sambungkan ke AP. Kalau koneksi tersimpan ada, NM pakai itu;
+// kalau belum, buat koneksi baru dengan password (kalau ada).
+pub fn connect_access_point(conn: &Connection, ap: &AccessPoint, password: Option<String>) -> Result<Option<Activation>, dbus::Error> {
+    let device = match wifi_device(conn)? {
+        Some(d) => d,
+        None => return Ok(None),
+    };
+    let ap_path = Path::new(ap.path.clone()).map_err(|e| dbus::Error::new_failed(&e))?;
+    let nm = conn.with_proxy(NM_BUS, NM_PATH, SLOW_TIMEOUT);
+
+    if ap.saved {
+        // "/" -> NM memilih koneksi tersimpan yang cocok dengan AP ini
+        let (active,): (Path<'static>,) = nm.method_call(
+            NM_IFACE,
+            "ActivateConnection",
+            (Path::from("/"), device.clone(), ap_path),
+        )?;
+        return Ok(Some(Activation { active, device, created: None }));
+    }
+
+    // 802.1X butuh identitas, sertifikat, dll -> tidak bisa dari picker
+    if ap.key_mgmt == KeyMgmt::Enterprise {
+        return Err(dbus::Error::new_failed("Jaringan 802.1X perlu diatur lewat nm-connection-editor"));
+    }
+
+    let mut settings: HashMap<&str, SettingsSection> = HashMap::new();
+    if let Some(security) = security_settings(ap.key_mgmt, password) {
+        settings.insert("802-11-wireless-security", security);
+    }
+
+    // Sisanya (SSID, mode, dll) dilengkapi NM dari data access point
+    let (created, active): (Path<'static>, Path<'static>) = nm.method_call(
+        NM_IFACE,
+        "AddAndActivateConnection",
+        (settings, device.clone(), ap_path),
+    )?;
+    Ok(Some(Activation { active, device, created: Some(created) }))
+}
+
+// NMDeviceStateReason yang umum saat gagal connect WiFi
+fn failure_reason(reason: u32) -> String {
+    match reason {
+        7 | 8 => "Password salah atau ditolak".to_string(),
+        9 | 10 => "wpa_supplicant gagal".to_string(),
+        11 => "Waktu habis saat menghubungkan".to_string(),
+        5 => "Tidak mendapat alamat IP".to_string(),
+        53 => "Jaringan tidak ditemukan".to_string(),
+        _ => format!("Gagal terhubung (alasan {})", reason),
+    }
+}
+
+// Status aktivasi sekarang. Objek koneksi aktif dihapus NM begitu gagal,
+// jadi alasannya diambil dari StateReason device.
+pub fn activation_state(conn: &Connection, activation: &Activation) -> ActivationState {
+    let state = conn.with_proxy(NM_BUS, activation.active.clone(), TIMEOUT)
+        .get::<u32>(ACTIVE_IFACE, "State");
+    match state {
+        Ok(ACTIVE_STATE_ACTIVATED) => ActivationState::Activated,
+        Ok(ACTIVE_STATE_DEACTIVATED) | Err(_) => {
+            let (_, reason) = conn.with_proxy(NM_BUS, activation.device.clone(), TIMEOUT)
+                .get::<(u32, u32)>(DEVICE_IFACE, "StateReason")
+                .unwrap_or((0, 0));
+            ActivationState::Failed(failure_reason(reason))
+        }
+        Ok(_) => ActivationState::Pending,
+    }
+}
+
+// Koneksi baru yang gagal dihapus lagi, supaya klik berikutnya minta password ulang
+pub fn forget_connection(conn: &Connection, path: &Path<'static>) -> Result<(), dbus::Error> {
+    conn.with_proxy(NM_BUS, path.clone(), TIMEOUT)
+        .method_call::<(), _, _, _>(SETTINGS_CONN_IFACE, "Delete", ())
+}
+
+pub fn disconnect_wifi(conn: &Connection) -> Result<(), dbus::Error> {
+    if let Some(device) = wifi_device(conn)? {
+        conn.with_proxy(NM_BUS, device, SLOW_TIMEOUT)
+            .method_call::<(), _, _, _>(DEVICE_IFACE, "Disconnect", ())?;
+    }
+    Ok(())
+}
+
+pub fn set_wireless_enabled(conn: &Connection, enabled: bool) -> Result<(), dbus::Error> {
+    conn.with_proxy(NM_BUS, NM_PATH, TIMEOUT).set(NM_IFACE, "WirelessEnabled", enabled)
+}
@@ -0,0 +1,377 @@
+// Popover pemilih WiFi: daftar AP, connect (dengan password), disconnect,
+// on/off radio, dan rescan. Semua panggilan NM jalan di worker thread.
+use super::nm::{self, AccessPoint, ActivationState, KeyMgmt};
+use crate::popover;
+use gtk4::prelude::*;
+use gtk4::{Box, Button, Label, Orientation, PasswordEntry, Popover, ScrolledWindow, Switch};
+use dbus::blocking::Connection;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Hasil scan butuh beberapa detik sebelum masuk daftar AP
+const SCAN_WAIT: Duration = Duration::from_secs(3);
+// Selama menunggu hasil aktivasi, worker bangun berkala untuk cek statusnya
+const ACTIVATION_POLL: Duration = Duration::from_millis(500);
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Perintah dari UI ke worker
+enum PickerCommand {
+    Refresh,
+    Scan,
+    Connect(AccessPoint, Option<String>),
+    Disconnect,
+    SetWireless(bool),
+}
+
+// Balasan worker ke UI
+enum PickerEvent {
+    AccessPoints(Vec<AccessPoint>, bool),
+    Error(String),
+}
+
+pub struct WifiPicker {
+    popover: Popover,
+    rescan_btn: Button,
+    commands: mpsc::Sender<PickerCommand>,
+}
+
+impl WifiPicker {
+    pub fn new(parent: &Box) -> Rc<Self> {
+        let (cmd_sender, cmd_receiver) = mpsc::channel();
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        thread::spawn(move || Self::run_worker(cmd_receiver, event_sender));
+
+        let content = Box::new(Orientation::Vertical, 8);
+        content.add_css_class("wifi-picker");
+
+        // --- HEADER: [WiFi] [rescan] [switch] ---
+        let header = Box::new(Orientation::Horizontal, 8);
+        let title = Label::new(Some("Wi-Fi"));
+        title.add_css_class("wifi-picker-title");
+        title.set_hexpand(true);
+        title.set_halign(gtk4::Align::Start);
+
+        let rescan_btn = Button::with_label("󰑐");
+        rescan_btn.add_css_class("wifi-picker-btn");
+        let radio_switch = Switch::new();
+        radio_switch.set_valign(gtk4::Align::Center);
+
+        header.append(&title);
+        header.append(&rescan_btn);
+        header.append(&radio_switch);
+
+        // --- DAFTAR AP ---
+        let list = Box::new(Orientation::Vertical, 2);
+        let scroller = ScrolledWindow::builder()
+            .child(&list)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .min_content_height(60)
+            .max_content_height(300)
+            .propagate_natural_height(true)
+            .build();
+
+        // --- PASSWORD (muncul saat memilih AP terkunci yang belum tersimpan) ---
+        let password_box = Box::new(Orientation::Vertical, 5);
+        password_box.add_css_class("wifi-password");
+        password_box.set_visible(false);
+        let password_label = Label::new(None);
+        password_label.set_halign(gtk4::Align::Start);
+        let password_entry = PasswordEntry::new();
+        password_entry.set_show_peek_icon(true);
+        let connect_btn = Button::with_label("Connect");
+        connect_btn.add_css_class("wifi-picker-btn");
+        password_box.append(&password_label);
+        password_box.append(&password_entry);
+        password_box.append(&connect_btn);
+
+        let status = Label::new(None);
+        status.add_css_class("wifi-picker-status");
+        status.set_visible(false);
+        status.set_wrap(true);
+
+        let disconnect_btn = Button::with_label("Disconnect");
+        disconnect_btn.add_css_class("wifi-picker-btn");
+        disconnect_btn.set_visible(false);
+
+        content.append(&header);
+        content.append(&scroller);
+        content.append(&password_box);
+        content.append(&status);
+        content.append(&disconnect_btn);
+
+        let popover = Popover::new();
+        popover.set_child(Some(&content));
+        popover.set_parent(parent);
+
+        // AP yang sedang menunggu password
+        let pending: Rc<RefCell<Option<AccessPoint>>> = Rc::new(RefCell::new(None));
+        // Switch radio ikut di-set_active() saat daftar AP dari worker masuk;
+        // jangan sampai itu dikirim balik sebagai SetWireless
+        let updating_switch = Rc::new(Cell::new(false));
+
+        let s = cmd_sender.clone();
+        rescan_btn.connect_clicked(move |button| Self::scan(&s, button));
+
+        let s = cmd_sender.clone();
+        let guard = updating_switch.clone();
+        radio_switch.connect_state_set(move |_, enabled| {
+            if !guard.get() {
+                let _ = s.send(PickerCommand::SetWireless(enabled));
+            }
+            glib::Propagation::Proceed
+        });
+
+        let s = cmd_sender.clone();
+        disconnect_btn.connect_clicked(move |_| { let _ = s.send(PickerCommand::Disconnect); });
+
+        // Connect dengan password (tombol atau Enter)
+        let submit = {
+            let s = cmd_sender.clone();
+            let pending = pending.clone();
+            let entry = password_entry.clone();
+            let password_box = password_box.clone();
+            let status = status.clone();
+            Rc::new(move || {
+                if let Some(ap) = pending.borrow_mut().take() {
+                    status.set_text(&format!("Menghubungkan ke {}...", ap.ssid));
+                    status.set_visible(true);
+                    let _ = s.send(PickerCommand::Connect(ap, Some(entry.text().to_string())));
+                }
+                entry.set_text("");
+                password_box.set_visible(false);
+            })
+        };
+        let submit_btn = submit.clone();
+        connect_btn.connect_clicked(move |_| submit_btn());
+        password_entry.connect_activate(move |_| submit());
+
+        // Terima hasil dari worker
+        let popover_weak = popover.downgrade();
+        let s = cmd_sender.clone();
+        glib::timeout_add_local(Duration::from_millis(100), move || {
+            if popover_weak.upgrade().is_none() {
+                return glib::ControlFlow::Break;
+            }
+            while let Ok(event) = event_receiver.try_recv() {
+                match event {
+                    PickerEvent::AccessPoints(aps, enabled) => {
+                        updating_switch.set(true);
+                        radio_switch.set_active(enabled);
+                        updating_switch.set(false);
+
+                        status.set_visible(false);
+                        disconnect_btn.set_visible(aps.iter().any(|ap| ap.active));
+                        Self::fill_list(&list, aps, &s, &pending, &password_box, &password_label, &password_entry, &status);
+                    }
+                    PickerEvent::Error(e) => {
+                        status.set_text(&e);
+                        status.set_visible(true);
+                    }
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+
+        Rc::new(Self { popover, rescan_btn, commands: cmd_sender })
+    }
+
+    // Minta scan lalu ambil ulang daftar AP setelah SCAN_WAIT, tanpa menahan worker
+    fn scan(commands: &mpsc::Sender<PickerCommand>, rescan_btn: &Button) {
+        let _ = commands.send(PickerCommand::Scan);
+        rescan_btn.set_sensitive(false);
+
+        let s = commands.clone();
+        let rescan_weak = rescan_btn.downgrade();
+        glib::timeout_add_local_once(SCAN_WAIT, move || {
+            let _ = s.send(PickerCommand::Refresh);
+            if let Some(button) = rescan_weak.upgrade() {
+                button.set_sensitive(true);
+            }
+        });
+    }
+
+    // Buka popover: tampilkan hasil scan terakhir dulu, lalu scan ulang
+    pub fn open(&self) {
+        let _ = self.commands.send(PickerCommand::Refresh);
+        Self::scan(&self.commands, &self.rescan_btn);
+        self.popover.popup();
+    }
+
+    fn signal_icon(strength: u8) -> &'static str {
+        match strength {
+            80..=100 => "󰤨",
+            60..=79  => "󰤥",
+            40..=59  => "󰤢",
+            20..=39  => "󰤟",
+            _        => "󰤯",
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn fill_list(
+        list: &Box,
+        aps: Vec<AccessPoint>,
+        commands: &mpsc::Sender<PickerCommand>,
+        pending: &Rc<RefCell<Option<AccessPoint>>>,
+        password_box: &Box,
+        password_label: &Label,
+        password_entry: &PasswordEntry,
+        status: &Label,
+    ) {
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+
+        if aps.is_empty() {
+            let empty = Label::new(Some("Tidak ada jaringan"));
+            empty.add_css_class("wifi-picker-status");
+            list.append(&empty);
+            return;
+        }
+
+        for ap in aps {
+            let row = Box::new(Orientation::Horizontal, 8);
+            let icon = Label::new(Some(Self::signal_icon(ap.strength)));
+            let ssid = Label::new(Some(&ap.ssid));
+            ssid.set_hexpand(true);
+            ssid.set_halign(gtk4::Align::Start);
+            ssid.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+            ssid.set_max_width_chars(24);
+            let security = Label::new(Some(if ap.is_secured() { "" } else { "" }));
+            security.set_tooltip_text(Some(&ap.security));
+            row.append(&icon);
+            row.append(&ssid);
+            row.append(&security);
+
+            let button = Button::builder().child(&row).build();
+            button.add_css_class("wifi-ap-row");
+            if ap.active {
+                button.add_css_class("active");
+            }
+
+            let s = commands.clone();
+            let pending = pending.clone();
+            let password_box = password_box.clone();
+            let password_label = password_label.clone();
+            let password_entry = password_entry.clone();
+            let status = status.clone();
+            button.connect_clicked(move |_| {
+                if ap.active {
+                    return;
+                }
+                // 802.1X butuh identitas/sertifikat, tidak cukup satu password
+                if ap.key_mgmt == KeyMgmt::Enterprise && !ap.saved {
+                    password_box.set_visible(false);
+                    status.set_text(&format!("{} memakai 802.1X (enterprise). Atur lewat nm-connection-editor.", ap.ssid));
+                    status.set_visible(true);
+                    return;
+                }
+                // AP terkunci & belum pernah disimpan -> minta password dulu
+                if ap.needs_password() && !ap.saved {
+                    password_label.set_text(&format!("Password untuk {}", ap.ssid));
+                    password_box.set_visible(true);
+                    password_entry.grab_focus();
+                    *pending.borrow_mut() = Some(ap.clone());
+                } else {
+                    let _ = s.send(PickerCommand::Connect(ap.clone(), None));
+                }
+            });
+
+            list.append(&button);
+        }
+    }
+
+    // Worker: satu koneksi system bus untuk semua aksi picker
+    fn run_worker(receiver: mpsc::Receiver<PickerCommand>, sender: mpsc::Sender<PickerEvent>) {
+        let conn = match Connection::new_system() {
+            Ok(c) => c,
+            Err(e) => {
+                let _ = sender.send(PickerEvent::Error(e.to_string()));
+                return;
+            }
+        };
+
+        // Aktivasi yang diterima NM tapi hasilnya belum diketahui: (aktivasi, SSID, batas waktu)
+        let mut activating: Option<(nm::Activation, String, Instant)> = None;
+
+        loop {
+            // Tanpa aktivasi yang ditunggu, cukup tidur sampai ada perintah
+            let command = if activating.is_some() {
+                match receiver.recv_timeout(ACTIVATION_POLL) {
+                    Ok(c) => Some(c),
+                    Err(mpsc::RecvTimeoutError::Timeout) => None,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            } else {
+                match receiver.recv() {
+                    Ok(c) => Some(c),
+                    Err(_) => return,
+                }
+            };
+
+            let mut changed = command.is_some();
+            let mut error = None;
+
+            if let Some(command) = command {
+                let result = match command {
+                    PickerCommand::Refresh => Ok(()),
+                    PickerCommand::Scan => nm::request_scan(&conn),
+                    PickerCommand::Connect(ap, password) => nm::connect_access_point(&conn, &ap, password)
+                        .map(|activation| {
+                            activating = activation.map(|a| (a, ap.ssid.clone(), Instant::now() + ACTIVATION_TIMEOUT));
+                        }),
+                    PickerCommand::Disconnect => {
+                        activating = None;
+                        nm::disconnect_wifi(&conn)
+                    }
+                    PickerCommand::SetWireless(enabled) => {
+                        activating = None;
+                        nm::set_wireless_enabled(&conn, enabled)
+                    }
+                };
+                if let Err(e) = result {
+                    error = Some(e.message().unwrap_or("NetworkManager error").to_string());
+                }
+            }
+
+            let finished = match &activating {
+                Some((activation, ssid, deadline)) => match nm::activation_state(&conn, activation) {
+                    ActivationState::Pending if Instant::now() < *deadline => false,
+                    ActivationState::Pending => {
+                        error = Some(format!("{}: waktu habis saat menghubungkan", ssid));
+                        true
+                    }
+                    ActivationState::Activated => true,
+                    ActivationState::Failed(reason) => {
+                        if let Some(created) = &activation.created {
+                            let _ = nm::forget_connection(&conn, created);
+                        }
+                        error = Some(format!("{}: {}", ssid, reason));
+                        true
+                    }
+                },
+                None => false,
+            };
+            if finished {
+                activating = None;
+                changed = true;
+            }
+
+            if !changed {
+                continue;
+            }
+
+            let event = match nm::list_access_points(&conn) {
+                Ok((aps, enabled)) => PickerEvent::AccessPoints(aps, enabled),
+                Err(e) => PickerEvent::Error(e.to_string()),
+            };
+            if !popover::send_state(&sender, event, error, PickerEvent::Error) {
+                return;
+            }
+        }
+    }
+}
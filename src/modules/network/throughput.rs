@@ -0,0 +1,84 @@
+// Kecepatan upload/download dari counter kernel
+// (/sys/class/net/<iface>/statistics/{rx,tx}_bytes), dihitung dari selisih antar sampel.
+use super::NetworkInfo;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+pub struct Throughput {
+    interface: Option<String>, // None = gabungan semua interface fisik
+    prev: Option<(u64, u64, Instant)>,
+}
+
+impl Throughput {
+    pub fn new(interface: Option<String>) -> Self {
+        Self { interface, prev: None }
+    }
+
+    // Interface virtual (bridge, veth, tunnel VPN) dilewati saat agregasi
+    // supaya trafik yang sama tidak terhitung dua kali
+    fn is_physical(name: &str) -> bool {
+        !Path::new("/sys/devices/virtual/net").join(name).exists()
+    }
+
+    fn read_counter(name: &str, counter: &str) -> u64 {
+        fs::read_to_string(format!("/sys/class/net/{}/statistics/{}", name, counter))
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn read_totals(&self) -> (u64, u64) {
+        if let Some(name) = &self.interface {
+            return (Self::read_counter(name, "rx_bytes"), Self::read_counter(name, "tx_bytes"));
+        }
+
+        let mut rx = 0;
+        let mut tx = 0;
+        if let Ok(entries) = fs::read_dir("/sys/class/net") {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == "lo" || !Self::is_physical(&name) { continue; }
+                rx += Self::read_counter(&name, "rx_bytes");
+                tx += Self::read_counter(&name, "tx_bytes");
+            }
+        }
+        (rx, tx)
+    }
+
+    // Isi rx/tx rate & total ke NetworkInfo
+    pub fn sample(&mut self, info: &mut NetworkInfo) {
+        let (rx, tx) = self.read_totals();
+        let now = Instant::now();
+
+        if let Some((prev_rx, prev_tx, prev_time)) = self.prev {
+            let secs = now.duration_since(prev_time).as_secs_f64();
+            if secs > 0.0 {
+                // saturating_sub: counter bisa reset saat interface down/up
+                info.rx_rate = rx.saturating_sub(prev_rx) as f64 / secs;
+                info.tx_rate = tx.saturating_sub(prev_tx) as f64 / secs;
+            }
+        }
+
+        info.rx_total = rx;
+        info.tx_total = tx;
+        self.prev = Some((rx, tx, now));
+    }
+}
+
+// 1536.0 -> "1.5KB"
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
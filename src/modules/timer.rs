@@ -0,0 +1,498 @@
+// Timer serbaguna: hitung mundur, stopwatch, dan pomodoro.
+// Klik kiri start/pause, klik kanan reset, klik tengah ganti mode, scroll ubah durasi.
+// Waktu disimpan sebagai detik unix (wall clock), jadi timer yang berjalan tetap
+// benar setelah bar di-restart atau laptop di-suspend. Update tiap detik memakai
+// mesin tick milik jam, dan hanya aktif selama timer berjalan.
+use gtk4::prelude::*;
+use gtk4::{Box, GestureClick, Label, Orientation};
+use super::WidgetModule;
+use super::clock::tick;
+use crate::config::TimerConfig;
+use crate::ipc;
+use crate::notify::{self, Urgency};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+// Batas fase pomodoro yang dikejar sekaligus (bar mati berjam-jam)
+const MAX_CATCH_UP: usize = 100;
+// Jeda sebelum menulis state, supaya scroll durasi beruntun cukup ditulis sekali
+const SAVE_DELAY: Duration = Duration::from_millis(300);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    Countdown,
+    Stopwatch,
+    Pomodoro,
+}
+
+impl Mode {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "countdown" => Some(Mode::Countdown),
+            "stopwatch" => Some(Mode::Stopwatch),
+            "pomodoro" => Some(Mode::Pomodoro),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Mode::Countdown => "countdown",
+            Mode::Stopwatch => "stopwatch",
+            Mode::Pomodoro => "pomodoro",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Mode::Countdown => Mode::Stopwatch,
+            Mode::Stopwatch => Mode::Pomodoro,
+            Mode::Pomodoro => Mode::Countdown,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Work => "Fokus",
+            Phase::ShortBreak => "Istirahat",
+            Phase::LongBreak => "Istirahat panjang",
+        }
+    }
+}
+
+// Yang disimpan ke disk
+#[derive(Serialize, Deserialize, Clone)]
+struct TimerState {
+    mode: Mode,
+    duration: i64,           // Detik: panjang hitung mundur / fase pomodoro saat ini
+    elapsed: i64,            // Detik yang terkumpul sampai pause terakhir
+    started_at: Option<i64>, // Detik unix saat mulai berjalan, None = berhenti
+    phase: Phase,
+    completed: u32,          // Sesi fokus pomodoro yang sudah selesai
+    #[serde(default)]
+    finished: bool,          // Hitung mundur baru selesai (ditandai sampai ada aksi)
+}
+
+struct Timer {
+    state: TimerState,
+    config: TimerConfig,
+}
+
+fn now() -> i64 {
+    Utc::now().timestamp()
+}
+
+// 90 -> "01:30", 3725 -> "1:02:05"
+fn format_secs(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+// Satu file per instance: "timer" -> timer.json, "timer#2" -> timer-2.json
+fn state_path(id: &str) -> Option<PathBuf> {
+    let state_home = std::env::var("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|h| PathBuf::from(h).join(".local/state")))
+        .ok()?;
+    Some(state_home.join("finshell").join(format!("{}.json", id.replace('#', "-"))))
+}
+
+// Penulis file state di worker thread (tulis file tidak boleh di GTK thread).
+// Hanya state terakhir yang ditulis; thread selesai saat sender di-drop.
+fn spawn_saver(path: PathBuf) -> mpsc::Sender<String> {
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        while let Ok(content) = receiver.recv() {
+            thread::sleep(SAVE_DELAY);
+            let content = receiver.try_iter().last().unwrap_or(content);
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            if let Err(e) = fs::write(&path, content) {
+                eprintln!("[Timer] Gagal menyimpan {}: {}", path.display(), e);
+            }
+        }
+    });
+    sender
+}
+
+impl Timer {
+    fn new(config: TimerConfig) -> Self {
+        let mode = Mode::parse(&config.mode).unwrap_or_else(|| {
+            eprintln!("[Timer] Mode '{}' tidak dikenal, pakai countdown", config.mode);
+            Mode::Countdown
+        });
+        let mut timer = Self {
+            state: TimerState {
+                mode,
+                duration: 0,
+                elapsed: 0,
+                started_at: None,
+                phase: Phase::Work,
+                completed: 0,
+                finished: false,
+            },
+            config,
+        };
+        timer.reset();
+        timer
+    }
+
+    // State terakhir dari disk (kalau persist), atau timer baru dari config
+    fn load(config: TimerConfig, path: Option<&Path>) -> Self {
+        let saved = path
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<TimerState>(&content).ok());
+        match saved {
+            Some(state) => Self { state, config },
+            None => Self::new(config),
+        }
+    }
+
+    fn phase_duration(&self, phase: Phase) -> i64 {
+        60 * match phase {
+            Phase::Work => self.config.work_mins,
+            Phase::ShortBreak => self.config.short_break_mins,
+            Phase::LongBreak => self.config.long_break_mins,
+        }
+    }
+
+    fn running(&self) -> bool {
+        self.state.started_at.is_some()
+    }
+
+    fn elapsed(&self, now: i64) -> i64 {
+        self.state.elapsed + self.state.started_at.map_or(0, |start| now - start)
+    }
+
+    // Angka yang ditampilkan: sisa waktu, atau waktu berjalan untuk stopwatch
+    fn display_secs(&self, now: i64) -> i64 {
+        match self.state.mode {
+            Mode::Stopwatch => self.elapsed(now),
+            _ => self.state.duration - self.elapsed(now),
+        }
+    }
+
+    fn start(&mut self, now: i64) {
+        if !self.running() {
+            self.state.started_at = Some(now);
+            self.state.finished = false;
+        }
+    }
+
+    fn pause(&mut self, now: i64) {
+        self.state.elapsed = self.elapsed(now);
+        self.state.started_at = None;
+    }
+
+    fn toggle(&mut self, now: i64) {
+        if self.running() {
+            self.pause(now);
+        } else {
+            self.start(now);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state.elapsed = 0;
+        self.state.started_at = None;
+        self.state.finished = false;
+        self.state.phase = Phase::Work;
+        self.state.completed = 0;
+        self.state.duration = match self.state.mode {
+            Mode::Countdown => 60 * self.config.duration_mins,
+            Mode::Stopwatch => 0,
+            Mode::Pomodoro => self.phase_duration(Phase::Work),
+        };
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.state.mode = mode;
+        self.reset();
+    }
+
+    // Durasi minimal 1 menit; stopwatch tidak punya durasi
+    fn set_duration(&mut self, secs: i64) {
+        if self.state.mode != Mode::Stopwatch {
+            self.state.duration = secs.max(60);
+            self.state.finished = false;
+        }
+    }
+
+    // Cek hitung mundur / fase yang habis. Return (judul, isi) notifikasi.
+    fn update(&mut self, now: i64) -> Option<(String, String)> {
+        let mut started = self.state.started_at?;
+        if self.state.mode == Mode::Stopwatch {
+            return None;
+        }
+
+        let mut finished = None;
+        for _ in 0..MAX_CATCH_UP {
+            let end = started + self.state.duration - self.state.elapsed;
+            if end > now {
+                break;
+            }
+
+            if self.state.mode == Mode::Countdown {
+                let body = format!("Hitung mundur {} selesai", format_secs(self.state.duration));
+                self.state.elapsed = 0;
+                self.state.started_at = None;
+                self.state.finished = true;
+                return Some(("Timer selesai".to_string(), body));
+            }
+
+            // Pomodoro: fase berikutnya langsung berjalan dari akhir fase sebelumnya
+            let next = match self.state.phase {
+                Phase::Work => {
+                    self.state.completed += 1;
+                    if self.state.completed % self.config.long_break_every.max(1) == 0 {
+                        Phase::LongBreak
+                    } else {
+                        Phase::ShortBreak
+                    }
+                }
+                Phase::ShortBreak | Phase::LongBreak => Phase::Work,
+            };
+            self.state.phase = next;
+            self.state.duration = self.phase_duration(next);
+            self.state.elapsed = 0;
+            started = end;
+
+            let minutes = self.state.duration / 60;
+            finished = Some(match next {
+                Phase::Work => ("Kembali fokus".to_string(), format!("Sesi fokus {} menit dimulai", minutes)),
+                _ => (
+                    format!("Waktunya {}", next.label().to_lowercase()),
+                    format!("{} sesi fokus selesai, istirahat {} menit", self.state.completed, minutes),
+                ),
+            });
+        }
+        self.state.started_at = Some(started);
+        finished
+    }
+
+    fn icon(&self) -> &'static str {
+        match (self.state.mode, self.state.phase) {
+            (Mode::Countdown, _) => "󰔛",
+            (Mode::Stopwatch, _) => "󱎫",
+            (Mode::Pomodoro, Phase::Work) => "🍅",
+            (Mode::Pomodoro, _) => "☕",
+        }
+    }
+
+    fn status(&self, now: i64) -> String {
+        let state = if self.running() { "running" } else { "paused" };
+        let phase = match self.state.mode {
+            Mode::Pomodoro => format!(" {}", self.state.phase.label().to_lowercase()),
+            _ => String::new(),
+        };
+        format!("{}{} {} {}", self.state.mode.name(), phase, format_secs(self.display_secs(now)), state)
+    }
+}
+
+// Bagian GTK: label + state, dipakai bersama oleh klik, scroll, tick, dan IPC
+struct TimerWidget {
+    timer: RefCell<Timer>,
+    container: Box,
+    label: Label,
+    ticking: Cell<bool>,
+    saver: Option<mpsc::Sender<String>>, // None = persist mati
+}
+
+impl TimerWidget {
+    fn save(&self) {
+        if let Some(saver) = &self.saver {
+            if let Ok(content) = serde_json::to_string(&self.timer.borrow().state) {
+                let _ = saver.send(content);
+            }
+        }
+    }
+
+    fn render(&self) {
+        let timer = self.timer.borrow();
+        let now = now();
+        self.label.set_text(&format!("{} {}", timer.icon(), format_secs(timer.display_secs(now))));
+
+        for class in ["running", "paused", "finished", "break"] {
+            self.container.remove_css_class(class);
+        }
+        if timer.running() {
+            self.container.add_css_class("running");
+        } else if timer.state.elapsed > 0 {
+            self.container.add_css_class("paused");
+        }
+        if timer.state.finished {
+            self.container.add_css_class("finished");
+        }
+        if timer.state.mode == Mode::Pomodoro && timer.state.phase != Phase::Work {
+            self.container.add_css_class("break");
+        }
+
+        let mut tooltip = timer.status(now);
+        if timer.state.mode == Mode::Pomodoro {
+            tooltip.push_str(&format!("\nSesi fokus selesai: {}", timer.state.completed));
+        }
+        tooltip.push_str("\nKlik: mulai/jeda · Klik kanan: reset\nKlik tengah: ganti mode · Scroll: ubah durasi");
+        self.container.set_tooltip_text(Some(&tooltip));
+    }
+
+    fn tick(&self) -> glib::ControlFlow {
+        let finished = self.timer.borrow_mut().update(now());
+        if let Some((summary, body)) = finished {
+            self.save();
+            if self.timer.borrow().config.notify {
+                // D-Bus blocking, jangan di GTK thread
+                thread::spawn(move || notify::send(&summary, &body, "alarm-symbolic", Urgency::Critical, 0));
+            }
+        }
+        self.render();
+
+        if self.timer.borrow().running() {
+            glib::ControlFlow::Continue
+        } else {
+            self.ticking.set(false);
+            glib::ControlFlow::Break
+        }
+    }
+
+    // Tick per detik hanya selama timer berjalan
+    fn ensure_ticking(self: &Rc<Self>) {
+        if !self.timer.borrow().running() || self.ticking.get() {
+            return;
+        }
+        self.ticking.set(true);
+        let weak = Rc::downgrade(self);
+        tick::start(None, 1, Rc::new(move || match weak.upgrade() {
+            Some(widget) => widget.tick(),
+            None => glib::ControlFlow::Break,
+        }));
+    }
+
+    fn apply(self: &Rc<Self>, action: impl FnOnce(&mut Timer, i64)) {
+        action(&mut self.timer.borrow_mut(), now());
+        self.save();
+        self.render();
+        self.ensure_ticking();
+    }
+
+    // Perintah IPC: finshell msg timer <perintah>
+    fn command(self: &Rc<Self>, args: &[&str]) -> Result<String, String> {
+        let minutes = |arg: Option<&&str>| -> Result<i64, String> {
+            arg.and_then(|m| m.parse::<i64>().ok())
+                .ok_or_else(|| "Butuh jumlah menit, misal: timer set 25".to_string())
+        };
+
+        match args.first().copied() {
+            Some("start") => self.apply(|t, now| t.start(now)),
+            Some("pause") => self.apply(|t, now| t.pause(now)),
+            Some("toggle") => self.apply(|t, now| t.toggle(now)),
+            Some("reset") => self.apply(|t, _| t.reset()),
+            Some("mode") => {
+                let mode = match args.get(1) {
+                    Some(name) => Mode::parse(name).ok_or_else(|| format!("Mode '{}' tidak dikenal", name))?,
+                    None => self.timer.borrow().state.mode.next(),
+                };
+                self.apply(|t, _| t.set_mode(mode));
+            }
+            Some("set") => {
+                let mins = minutes(args.get(1))?;
+                self.apply(|t, _| t.set_duration(mins * 60));
+            }
+            Some("add") => {
+                let mins = minutes(args.get(1))?;
+                self.apply(|t, _| t.set_duration(t.state.duration + mins * 60));
+            }
+            Some("status") => {}
+            _ => return Err("Perintah: start, pause, toggle, reset, mode [nama], set <menit>, add <menit>, status".to_string()),
+        }
+        Ok(self.timer.borrow().status(now()))
+    }
+}
+
+pub struct TimerModule {
+    config: TimerConfig,
+}
+
+impl TimerModule {
+    pub fn new(config: TimerConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl WidgetModule for TimerModule {
+    fn build_widget(&self) -> gtk4::Widget {
+        let container = Box::new(Orientation::Horizontal, 0);
+        container.add_css_class("timer-widget");
+
+        let label = Label::new(None);
+        container.append(&label);
+
+        // Nama IPC ("timer", "timer#2", ...) sekaligus nama file state, supaya
+        // beberapa timer tidak saling menimpa
+        let id = ipc::available_id("timer");
+        let path = if self.config.persist { state_path(&id) } else { None };
+        let widget = Rc::new(TimerWidget {
+            timer: RefCell::new(Timer::load(self.config.clone(), path.as_deref())),
+            container: container.clone(),
+            label,
+            ticking: Cell::new(false),
+            saver: path.map(spawn_saver),
+        });
+
+        // Timer yang masih berjalan sebelum restart: kejar fase yang terlewat
+        let _ = widget.tick();
+        widget.ensure_ticking();
+
+        let click = GestureClick::new();
+        click.set_button(0);
+        let w = widget.clone();
+        click.connect_pressed(move |gesture, _, _, _| {
+            match gesture.current_button() {
+                1 => w.apply(|t, now| t.toggle(now)),
+                2 => w.apply(|t, _| t.set_mode(t.state.mode.next())),
+                3 => w.apply(|t, _| t.reset()),
+                _ => {}
+            }
+        });
+        container.add_controller(click);
+
+        let step = self.config.step_mins.max(1) * 60;
+        let scroll = gtk4::EventControllerScroll::new(gtk4::EventControllerScrollFlags::VERTICAL);
+        let w = widget.clone();
+        scroll.connect_scroll(move |_, _, dy| {
+            let delta = if dy > 0.0 { -step } else { step };
+            w.apply(|t, _| t.set_duration(t.state.duration + delta));
+            gtk4::glib::Propagation::Stop
+        });
+        container.add_controller(scroll);
+
+        ipc::register(&id, move |args| widget.command(args));
+        if id != "timer" {
+            eprintln!("[Timer] Ada lebih dari satu timer, instance ini dikontrol lewat `finshell msg {} ...`", id);
+        }
+
+        container.upcast()
+    }
+}
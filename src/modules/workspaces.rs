@@ -1,120 +1,188 @@
 use gtk4::prelude::*;
 use gtk4::{Box, Button, Orientation};
 use super::WidgetModule;
-use std::process::Command;
+use crate::config::WorkspacesConfig;
+use hyprland::{Event, Workspace};
+use std::collections::{BTreeMap, HashMap};
 use std::thread;
-use std::io::{BufRead, BufReader};
-use std::os::unix::net::UnixStream;
-use std::env;
-use std::path::PathBuf;
-use serde::Deserialize;
 use std::sync::mpsc; // Gunakan channel standar Rust
 
-#[derive(Deserialize, Debug, Clone)]
-struct WorkspaceData {
-    id: i32,
-    #[serde(skip)] // Ignore field name
-    _name: String, 
+mod hyprland;
+
+// Model workspace di GTK thread. Diubah sedikit demi sedikit dari event Hyprland,
+// tombol ditambah/dihapus/dipindah di tempat (tidak dibangun ulang semua).
+// Container tidak disimpan di sini (dioper per event), supaya polling di bawah
+// cukup memegang referensi lemah dan berhenti saat widget dihancurkan.
+struct WorkspaceView {
+    config: WorkspacesConfig,
+    workspaces: BTreeMap<i32, Workspace>, // Urut berdasarkan id
+    active: i32,
+    buttons: HashMap<i32, Button>,        // Identitas tombol = id workspace, bukan teks label
 }
 
-#[derive(Deserialize, Debug, Clone)]
-struct ActiveWorkspaceData {
-    id: i32,
-}
-
-enum IpcEvent {
-    WorkspaceChanged(i32),
-    WorkspacesListChanged,
-    DataRefreshed(Vec<WorkspaceData>, i32),
-    Error(String),
-}
-
-pub struct WorkspacesModule;
+impl WorkspaceView {
+    fn new(config: WorkspacesConfig) -> Self {
+        Self {
+            config,
+            workspaces: BTreeMap::new(),
+            active: 0,
+            buttons: HashMap::new(),
+        }
+    }
 
-impl WorkspacesModule {
-    fn get_event_socket_path() -> Result<PathBuf, String> {
-        let xdg_runtime = env::var("XDG_RUNTIME_DIR")
-            .map_err(|_| "XDG_RUNTIME_DIR not set")?;
-        
-        let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE")
-            .map_err(|_| "HYPRLAND_INSTANCE_SIGNATURE not set")?;
-        
-        let path = PathBuf::from(format!("{}/hypr/{}/.socket2.sock", xdg_runtime, signature));
-        if !path.exists() {
-            return Err(format!("Socket not found at: {:?}", path));
+    fn apply(&mut self, container: &Box, event: Event) {
+        match event {
+            Event::Snapshot(workspaces, active) => {
+                self.workspaces = workspaces.into_iter().map(|w| (w.id, w)).collect();
+                self.active = active;
+            }
+            Event::Created(workspace) => {
+                self.workspaces.insert(workspace.id, workspace);
+            }
+            Event::Destroyed(id) => {
+                self.workspaces.remove(&id);
+            }
+            Event::Activated(id) => self.active = id,
+            Event::Renamed(id, name) => {
+                if let Some(workspace) = self.workspaces.get_mut(&id) {
+                    workspace.name = name;
+                }
+            }
+            Event::Moved(id, monitor) => {
+                if let Some(workspace) = self.workspaces.get_mut(&id) {
+                    workspace.monitor = monitor;
+                }
+            }
+            Event::Error(e) => {
+                eprintln!("[Workspaces] {}", e);
+                return;
+            }
         }
-        Ok(path)
+        self.sync(container);
     }
 
-    fn fetch_full_state() -> Result<(Vec<WorkspaceData>, i32), String> {
-        let output_ws = Command::new("hyprctl")
-            .args(["workspaces", "-j"])
-            .output()
-            .map_err(|e| e.to_string())?;
+    // Special workspace: id negatif, nama "special:<nama>"
+    fn special_name(workspace: &Workspace) -> Option<&str> {
+        if workspace.id >= 0 {
+            return None;
+        }
+        Some(workspace.name.strip_prefix("special:").unwrap_or(&workspace.name))
+    }
 
-        let output_active = Command::new("hyprctl")
-            .args(["activeworkspace", "-j"])
-            .output()
-            .map_err(|e| e.to_string())?;
+    // Label: [workspaces.labels] -> nama Hyprland (kalau bukan angka id) -> id
+    fn label(&self, id: i32) -> String {
+        if let Some(label) = self.config.labels.get(&id.to_string()) {
+            return label.clone();
+        }
+        if let Some(workspace) = self.workspaces.get(&id).filter(|_| self.config.use_names) {
+            let name = Self::special_name(workspace).unwrap_or(&workspace.name);
+            if !name.is_empty() {
+                return name.to_string();
+            }
+        }
+        id.to_string()
+    }
 
-        let workspaces: Vec<WorkspaceData> = serde_json::from_slice(&output_ws.stdout)
-            .map_err(|_| "Failed to parse workspaces JSON")?;
-            
-        let active: ActiveWorkspaceData = serde_json::from_slice(&output_active.stdout)
-            .map_err(|_| "Failed to parse activeworkspace JSON")?;
+    // Monitor tempat workspace persisten `id` seharusnya dibuat (dari persistent_monitors)
+    fn bound_monitor(&self, id: i32) -> Option<String> {
+        let monitor = self.config.monitor.as_ref()?;
+        self.config.persistent_monitors.get(monitor)
+            .filter(|ids| ids.contains(&id))
+            .map(|_| monitor.clone())
+    }
 
-        Ok((workspaces, active.id))
+    fn create_button(workspace: Option<&Workspace>, id: i32, monitor: Option<String>) -> Button {
+        let button = Button::builder()
+            .css_classes(vec!["workspace-button".to_string()])
+            .build();
+        // Special workspace dibuka dengan togglespecialworkspace, bukan lewat id
+        let special = workspace.and_then(Self::special_name).map(str::to_string);
+        button.connect_clicked(move |_| match (&special, &monitor) {
+            (Some(name), _) => hyprland::dispatch("togglespecialworkspace", name),
+            // Workspace yang belum ada dibuat Hyprland di monitor yang sedang fokus,
+            // jadi fokuskan dulu monitor tujuannya
+            (None, Some(monitor)) => hyprland::dispatch_all(&[("focusmonitor", monitor.as_str()), ("workspace", &id.to_string())]),
+            (None, None) => hyprland::dispatch("workspace", &id.to_string()),
+        });
+        button
     }
 
-    fn rebuild_ui(container: &Box, workspaces: Vec<WorkspaceData>, active_id: i32) {
-        while let Some(child) = container.first_child() {
-            container.remove(&child);
+    // Workspace yang ditampilkan: yang ada (sesuai filter) + yang persisten
+    fn visible(&self) -> Vec<i32> {
+        let monitor = self.config.monitor.as_deref();
+        let mut ids: Vec<i32> = self.workspaces.values()
+            .filter(|w| w.id >= 0 || self.config.show_special)
+            .filter(|w| monitor.is_none_or(|m| w.monitor == m))
+            .map(|w| w.id)
+            .collect();
+
+        ids.extend(&self.config.persistent);
+        // Daftar per monitor hanya untuk bar yang difilter ke monitor itu
+        if let Some(persistent) = monitor.and_then(|m| self.config.persistent_monitors.get(m)) {
+            ids.extend(persistent);
         }
 
-        let mut sorted_ws = workspaces;
-        sorted_ws.sort_by_key(|w| w.id);
-
-        for ws in sorted_ws {
-            let id = ws.id;
-            if id < 0 { continue; }
+        // Workspace biasa urut id, special di belakang
+        ids.sort_by_key(|id| (*id < 0, id.abs()));
+        ids.dedup();
+        ids
+    }
 
-            let button = Button::builder()
-                .label(id.to_string())
-                .css_classes(vec!["workspace-button".to_string()])
-                .build();
+    // Samakan tombol dengan model: buat yang baru, buang yang hilang, urutkan
+    fn sync(&mut self, container: &Box) {
+        let visible = self.visible();
 
-            if id == active_id {
-                button.add_css_class("active");
+        self.buttons.retain(|id, button| {
+            let keep = visible.contains(id);
+            if !keep {
+                container.remove(button);
             }
+            keep
+        });
 
-            let button_clone = button.clone();
-            button.connect_clicked(move |_| {
-                button_clone.add_css_class("active"); 
-                let _ = Command::new("hyprctl")
-                    .args(["dispatch", "workspace", &id.to_string()])
-                    .spawn();
+        let mut previous: Option<Button> = None;
+        for id in visible {
+            let workspace = self.workspaces.get(&id);
+            let label = self.label(id);
+            let monitor = self.bound_monitor(id);
+            let button = self.buttons.entry(id).or_insert_with(|| {
+                let button = Self::create_button(workspace, id, monitor);
+                container.append(&button);
+                button
             });
+            container.reorder_child_after(button, previous.as_ref());
 
-            container.append(&button);
+            if button.label().as_deref() != Some(label.as_str()) {
+                button.set_label(&label);
+            }
+            if id == self.active {
+                button.add_css_class("active");
+            } else {
+                button.remove_css_class("active");
+            }
+            // Persisten tapi belum dibuat Hyprland
+            if workspace.is_some() {
+                button.remove_css_class("empty");
+            } else {
+                button.add_css_class("empty");
+            }
+            let tooltip = match workspace {
+                Some(w) => format!("{} ({})", w.name, w.monitor),
+                None => format!("Workspace {}", id),
+            };
+            button.set_tooltip_text(Some(&tooltip));
+            previous = Some(button.clone());
         }
     }
+}
 
-    fn update_active_state(container: &Box, active_id: i32) {
-        let mut child = container.first_child();
-        while let Some(widget) = child {
-            if let Some(button) = widget.downcast_ref::<Button>() {
-                if let Some(label) = button.label() {
-                    if let Ok(id) = label.parse::<i32>() {
-                        if id == active_id {
-                            button.add_css_class("active");
-                        } else {
-                            button.remove_css_class("active");
-                        }
-                    }
-                }
-            }
-            child = widget.next_sibling();
-        }
+pub struct WorkspacesModule {
+    config: WorkspacesConfig,
+}
+
+impl WorkspacesModule {
+    pub fn new(config: WorkspacesConfig) -> Self {
+        Self { config }
     }
 }
 
@@ -122,88 +190,29 @@ impl WidgetModule for WorkspacesModule {
     fn build_widget(&self) -> gtk4::Widget {
         let container = Box::new(Orientation::Horizontal, 5);
         container.add_css_class("workspaces-widget");
-        let container_weak = container.downgrade();
 
-        // REPLACEMENT: Gunakan std::sync::mpsc channel
-        // Ini adalah cara standar Rust, tidak terpengaruh versi library
+        // Semua I/O ke Hyprland (snapshot & event) ada di worker thread
         let (sender, receiver) = mpsc::channel();
-        let sender_clone = sender.clone();
+        thread::spawn(move || hyprland::run(sender));
 
-        // Thread 1: Fetch Awal
-        thread::spawn(move || {
-            match Self::fetch_full_state() {
-                Ok((ws, active)) => {
-                    let _ = sender_clone.send(IpcEvent::DataRefreshed(ws, active));
-                }
-                Err(e) => {
-                    let _ = sender_clone.send(IpcEvent::Error(e));
-                }
-            }
-        });
-
-        // Thread 2: Socket Listener
-        thread::spawn(move || {
-            let socket_path = match Self::get_event_socket_path() {
-                Ok(p) => p,
-                Err(e) => {
-                    let _ = sender.send(IpcEvent::Error(e));
-                    return;
-                }
-            };
-
-            loop {
-                if let Ok(stream) = UnixStream::connect(&socket_path) {
-                    let reader = BufReader::new(stream);
-                    for line in reader.lines().flatten() {
-                        if line.starts_with("workspace>>") {
-                            if let Some(data) = line.split(">>").nth(1) {
-                                if let Ok(id) = data.parse::<i32>() {
-                                        let _ = sender.send(IpcEvent::WorkspaceChanged(id));
-                                } else {
-                                        let _ = sender.send(IpcEvent::WorkspacesListChanged);
-                                }
-                            }
-                        } else if line.starts_with("createworkspace>>") 
-                                || line.starts_with("destroyworkspace>>") {
-                            let _ = sender.send(IpcEvent::WorkspacesListChanged);
-                        }
-                    }
-                } else {
-                    thread::sleep(std::time::Duration::from_secs(2));
-                }
-            }
-        });
+        let mut view = WorkspaceView::new(self.config.clone());
+        let container_weak = container.downgrade();
 
         // UI Thread: Cek inbox setiap 100ms
         // try_recv() itu non-blocking (instan), jadi tidak bikin berat UI
         glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-            if let Some(container) = container_weak.upgrade() {
-                // Proses SEMUA pesan yang menumpuk di inbox (drain channel)
-                while let Ok(msg) = receiver.try_recv() {
-                    match msg {
-                        IpcEvent::DataRefreshed(ws, active) => {
-                            Self::rebuild_ui(&container, ws, active);
-                        }
-                        IpcEvent::WorkspaceChanged(active_id) => {
-                            Self::update_active_state(&container, active_id);
-                        }
-                        IpcEvent::WorkspacesListChanged => {
-                            // Quick refresh
-                            thread::spawn(move || {
-                                let _ = Command::new("hyprctl").arg("reload").output();
-                            });
-                             // Trigger fetch ulang manual di UI thread
-                             if let Ok((ws, active)) = Self::fetch_full_state() {
-                                Self::rebuild_ui(&container, ws, active);
-                             }
-                        }
-                        IpcEvent::Error(e) => eprintln!("[Workspaces] {}", e),
-                    }
-                }
+            // Widget sudah dihancurkan: berhenti, receiver di-drop dan worker ikut selesai
+            let container = match container_weak.upgrade() {
+                Some(c) => c,
+                None => return glib::ControlFlow::Break,
+            };
+            // Proses SEMUA pesan yang menumpuk di inbox (drain channel)
+            for event in receiver.try_iter() {
+                view.apply(&container, event);
             }
             glib::ControlFlow::Continue
         });
 
         container.upcast()
     }
-}
\ No newline at end of file
+}
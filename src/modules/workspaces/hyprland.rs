@@ -0,0 +1,166 @@
+// IPC Hyprland tanpa spawn hyprctl: socket request (.socket.sock) untuk snapshot
+// awal, socket event (.socket2.sock) untuk perubahan. Semua blocking, jadi hanya
+// dipanggil dari worker thread.
+use serde::Deserialize;
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Deserialize, Clone)]
+pub struct Workspace {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub monitor: String,
+}
+
+#[derive(Deserialize)]
+struct ActiveWorkspace {
+    id: i32,
+    #[serde(default)]
+    monitor: String,
+}
+
+// Perubahan yang dikirim ke UI, diterapkan satu per satu ke model
+pub enum Event {
+    Snapshot(Vec<Workspace>, i32), // Semua workspace + id yang aktif (awal / reconnect)
+    Created(Workspace),
+    Destroyed(i32),
+    Activated(i32),
+    Renamed(i32, String),
+    Moved(i32, String), // Workspace pindah monitor
+    Error(String),
+}
+
+fn socket_path(name: &str) -> Result<PathBuf, String> {
+    let xdg_runtime = env::var("XDG_RUNTIME_DIR")
+        .map_err(|_| "XDG_RUNTIME_DIR not set")?;
+
+    let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE")
+        .map_err(|_| "HYPRLAND_INSTANCE_SIGNATURE not set")?;
+
+    let path = PathBuf::from(format!("{}/hypr/{}/{}", xdg_runtime, signature, name));
+    if !path.exists() {
+        return Err(format!("Socket not found at: {:?}", path));
+    }
+    Ok(path)
+}
+
+// Sama dengan `hyprctl <command>`, balasan dibaca sampai socket ditutup
+fn request(command: &str) -> Result<String, String> {
+    let mut stream = UnixStream::connect(socket_path(".socket.sock")?).map_err(|e| e.to_string())?;
+    stream.write_all(command.as_bytes()).map_err(|e| e.to_string())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(|e| e.to_string())?;
+    Ok(response)
+}
+
+fn fetch_snapshot() -> Result<(Vec<Workspace>, ActiveWorkspace), String> {
+    let workspaces: Vec<Workspace> = serde_json::from_str(&request("j/workspaces")?)
+        .map_err(|_| "Failed to parse workspaces JSON")?;
+    let active: ActiveWorkspace = serde_json::from_str(&request("j/activeworkspace")?)
+        .map_err(|_| "Failed to parse activeworkspace JSON")?;
+    Ok((workspaces, active))
+}
+
+// "ID,NAME" (nama boleh mengandung koma)
+fn id_and_name(data: &str) -> Option<(i32, String)> {
+    let (id, name) = data.split_once(',')?;
+    Some((id.parse().ok()?, name.to_string()))
+}
+
+// Satu baris socket2 "event>>data" -> perubahan model (None = tidak relevan)
+fn parse_event(line: &str, focused_monitor: &mut String) -> Option<Event> {
+    let (event, data) = line.split_once(">>")?;
+    match event {
+        "workspacev2" => id_and_name(data).map(|(id, _)| Event::Activated(id)),
+        "createworkspacev2" => id_and_name(data).map(|(id, name)| {
+            // Event ini tidak membawa monitor; workspace baru dibuat di monitor yang fokus
+            Event::Created(Workspace { id, name, monitor: focused_monitor.clone() })
+        }),
+        "destroyworkspacev2" => id_and_name(data).map(|(id, _)| Event::Destroyed(id)),
+        "renameworkspace" => id_and_name(data).map(|(id, name)| Event::Renamed(id, name)),
+        "moveworkspacev2" => {
+            // ID,NAME,MONITOR
+            let (rest, monitor) = data.rsplit_once(',')?;
+            id_and_name(rest).map(|(id, _)| Event::Moved(id, monitor.to_string()))
+        }
+        "focusedmonv2" => {
+            // MONITOR,WORKSPACE_ID
+            let (monitor, id) = data.rsplit_once(',')?;
+            *focused_monitor = monitor.to_string();
+            id.parse().ok().map(Event::Activated)
+        }
+        _ => None,
+    }
+}
+
+// Worker: sambung ke socket2, kirim snapshot, lalu alirkan perubahan.
+// Kalau Hyprland restart / socket putus, sambung ulang dan snapshot lagi.
+pub fn run(sender: mpsc::Sender<Event>) {
+    let socket_path = match socket_path(".socket2.sock") {
+        Ok(p) => p,
+        Err(e) => {
+            let _ = sender.send(Event::Error(e));
+            return;
+        }
+    };
+
+    loop {
+        let stream = match UnixStream::connect(&socket_path) {
+            Ok(s) => s,
+            Err(_) => {
+                thread::sleep(RECONNECT_DELAY);
+                continue;
+            }
+        };
+
+        // Snapshot setelah tersambung supaya tidak ada event yang terlewat di antaranya
+        let mut focused_monitor = String::new();
+        let snapshot = match fetch_snapshot() {
+            Ok((workspaces, active)) => {
+                focused_monitor = active.monitor;
+                Event::Snapshot(workspaces, active.id)
+            }
+            Err(e) => Event::Error(e),
+        };
+        if sender.send(snapshot).is_err() {
+            return;
+        }
+
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if let Some(event) = parse_event(&line, &mut focused_monitor) {
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+        }
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+// `hyprctl dispatch <dispatcher> <arg>` (dari klik tombol), dijalankan di thread terpisah
+pub fn dispatch(dispatcher: &str, arg: &str) {
+    dispatch_all(&[(dispatcher, arg)]);
+}
+
+// Beberapa dispatch berurutan di satu thread, misal focusmonitor dulu baru workspace
+pub fn dispatch_all(commands: &[(&str, &str)]) {
+    let commands: Vec<String> = commands.iter()
+        .map(|(dispatcher, arg)| format!("dispatch {} {}", dispatcher, arg))
+        .collect();
+    thread::spawn(move || {
+        for command in commands {
+            if let Err(e) = request(&command) {
+                eprintln!("[Workspaces] {}", e);
+                return;
+            }
+        }
+    });
+}
@@ -0,0 +1,47 @@
+// Notifikasi desktop via org.freedesktop.Notifications (session bus),
+// tanpa spawn notify-send. Panggilan D-Bus-nya blocking, jadi panggil
+// dari worker thread, bukan dari GTK thread.
+use dbus::arg::{PropMap, RefArg, Variant};
+use dbus::blocking::Connection;
+use std::time::Duration;
+
+const NOTIFY_BUS: &str = "org.freedesktop.Notifications";
+const NOTIFY_PATH: &str = "/org/freedesktop/Notifications";
+const NOTIFY_IFACE: &str = "org.freedesktop.Notifications";
+const TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Clone, Copy)]
+pub enum Urgency {
+    Normal = 1,
+    Critical = 2, // Tidak hilang sendiri di kebanyakan daemon notifikasi
+}
+
+// Kirim notifikasi. `replaces` = id notifikasi lama yang mau ditimpa (0 = baru).
+// Return id notifikasi (0 kalau gagal), simpan untuk update berikutnya.
+pub fn send(summary: &str, body: &str, icon: &str, urgency: Urgency, replaces: u32) -> u32 {
+    let conn = match Connection::new_session() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("[Notify] Session bus tidak tersedia: {}", e);
+            return 0;
+        }
+    };
+
+    let mut hints = PropMap::new();
+    hints.insert("urgency".to_string(), Variant(Box::new(urgency as u8) as Box<dyn RefArg>));
+
+    let proxy = conn.with_proxy(NOTIFY_BUS, NOTIFY_PATH, TIMEOUT);
+    let result: Result<(u32,), dbus::Error> = proxy.method_call(
+        NOTIFY_IFACE,
+        "Notify",
+        ("Finshell", replaces, icon, summary, body, Vec::<String>::new(), hints, -1i32),
+    );
+
+    match result {
+        Ok((id,)) => id,
+        Err(e) => {
+            eprintln!("[Notify] Gagal mengirim notifikasi: {}", e);
+            0
+        }
+    }
+}
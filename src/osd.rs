@@ -0,0 +1,194 @@
+// On-Screen Display (OSD): popup di tengah layar saat volume/brightness berubah.
+// Ini bukan modul bar, tapi surface layer-shell terpisah yang dipakai bersama.
+use gtk4::prelude::*;
+use gtk4::{Application, ApplicationWindow, Box, Label, Orientation, ProgressBar};
+use gtk4_layer_shell::{KeyboardMode, Layer, LayerShell};
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use crate::config::OsdConfig;
+use crate::modules::audio::AudioModule;
+
+struct Osd {
+    window: ApplicationWindow,
+    container: Box,
+    icon: Label,
+    bar: ProgressBar,
+    value: Label,
+    timeout: Duration,
+    hide_timer: Option<glib::SourceId>,
+}
+
+// OSD hanya hidup di GTK thread, jadi cukup thread_local (tanpa Mutex)
+thread_local! {
+    static OSD: RefCell<Option<Osd>> = const { RefCell::new(None) };
+}
+
+impl Osd {
+    fn build(app: &Application, config: &OsdConfig) -> Self {
+        let window = ApplicationWindow::builder()
+            .application(app)
+            .title("Finshell OSD")
+            .build();
+
+        window.init_layer_shell();
+        window.set_namespace("finshell-osd");
+        window.set_layer(Layer::Overlay);
+        window.set_keyboard_mode(KeyboardMode::None);
+        // Tanpa anchor -> compositor menaruh surface di tengah layar
+
+        let container = Box::new(Orientation::Horizontal, 12);
+        container.add_css_class("osd");
+
+        let icon = Label::new(None);
+        icon.add_css_class("osd-icon");
+
+        let bar = ProgressBar::new();
+        bar.add_css_class("osd-bar");
+        bar.set_hexpand(true);
+        bar.set_valign(gtk4::Align::Center);
+
+        let value = Label::new(None);
+        value.add_css_class("osd-value");
+
+        container.append(&icon);
+        container.append(&bar);
+        container.append(&value);
+
+        window.set_child(Some(&container));
+        window.set_visible(false);
+
+        Self {
+            window,
+            container,
+            icon,
+            bar,
+            value,
+            timeout: Duration::from_millis(config.timeout_ms),
+            hide_timer: None,
+        }
+    }
+
+    fn show(&mut self, icon: &str, percent: i32, css_class: &str) {
+        self.icon.set_text(icon);
+        self.value.set_text(&format!("{}%", percent));
+        // Volume bisa > 100% (wpctl --limit 1.5), bar-nya tetap mentok penuh
+        self.bar.set_fraction(percent.clamp(0, 100) as f64 / 100.0);
+
+        for class in ["volume", "muted", "brightness"] {
+            self.container.remove_css_class(class);
+        }
+        self.container.add_css_class(css_class);
+
+        self.window.set_visible(true);
+
+        // Reset timer supaya OSD tidak hilang saat tombol ditekan beruntun
+        if let Some(source_id) = self.hide_timer.take() {
+            source_id.remove();
+        }
+
+        let source_id = glib::timeout_add_local_once(self.timeout, || {
+            OSD.with(|cell| {
+                if let Some(osd) = cell.borrow_mut().as_mut() {
+                    osd.window.set_visible(false);
+                    osd.hide_timer = None;
+                }
+            });
+        });
+        self.hide_timer = Some(source_id);
+    }
+}
+
+// Dipanggil sekali dari main() setelah CSS dimuat
+pub fn init(app: &Application, config: &OsdConfig) {
+    if !config.enabled {
+        return;
+    }
+
+    let osd = Osd::build(app, config);
+    OSD.with(|cell| *cell.borrow_mut() = Some(osd));
+
+    if config.backlight {
+        watch_backlight();
+    }
+}
+
+pub fn show_volume(volume: i32, is_muted: bool) {
+    let icon = AudioModule::get_icon(volume, is_muted);
+    let css_class = if is_muted { "muted" } else { "volume" };
+    show(icon, volume, css_class);
+}
+
+pub fn show_brightness(percent: i32) {
+    show("󰃠", percent, "brightness");
+}
+
+fn show(icon: &str, percent: i32, css_class: &str) {
+    OSD.with(|cell| {
+        if let Some(osd) = cell.borrow_mut().as_mut() {
+            osd.show(icon, percent, css_class);
+        }
+    });
+}
+
+// --- BACKLIGHT ---
+// Cari device backlight pertama (intel_backlight, amdgpu_bl0, dll)
+fn find_backlight_device() -> Option<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir("/sys/class/backlight")
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    entries.sort();
+    entries.into_iter().next()
+}
+
+fn read_backlight_percent(device: &Path) -> Option<i32> {
+    let read = |file: &str| -> Option<f64> {
+        fs::read_to_string(device.join(file)).ok()?.trim().parse().ok()
+    };
+
+    let current = read("actual_brightness").or_else(|| read("brightness"))?;
+    let max = read("max_brightness")?;
+    if max <= 0.0 {
+        return None;
+    }
+    Some((current / max * 100.0).round() as i32)
+}
+
+fn watch_backlight() {
+    let device = match find_backlight_device() {
+        Some(d) => d,
+        None => return, // Desktop tanpa backlight
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    // Worker Thread: sysfs backlight tidak selalu kirim event inotify, jadi polling ringan
+    thread::spawn(move || {
+        let mut last = read_backlight_percent(&device);
+        loop {
+            thread::sleep(Duration::from_millis(200));
+            let current = read_backlight_percent(&device);
+            if current != last {
+                if let Some(percent) = current {
+                    if sender.send(percent).is_err() {
+                        break;
+                    }
+                }
+                last = current;
+            }
+        }
+    });
+
+    glib::timeout_add_local(Duration::from_millis(100), move || {
+        // Ambil nilai terakhir saja kalau ada beberapa yang menumpuk
+        if let Some(percent) = receiver.try_iter().last() {
+            show_brightness(percent);
+        }
+        glib::ControlFlow::Continue
+    });
+}
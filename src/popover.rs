@@ -0,0 +1,33 @@
+// Popover GTK4 adalah anak dari widget induknya, jadi klik/scroll di dalam popover
+// ikut naik (bubble) ke controller yang dipasang di widget induk itu.
+// Popover punya surface sendiri: event yang surface-nya bukan surface widget
+// pemilik controller pasti berasal dari dalam popover.
+use gtk4::prelude::*;
+use gtk4::EventController;
+use std::sync::mpsc;
+
+// True kalau event yang sedang diproses `controller` datang dari popover anak widgetnya
+pub fn from_popover(controller: &impl IsA<EventController>) -> bool {
+    let event_surface = controller.current_event().and_then(|event| event.surface());
+    let own_surface = controller.widget()
+        .and_then(|widget| widget.native())
+        .and_then(|native| native.surface());
+    match (event_surface, own_surface) {
+        (Some(event), Some(own)) => event != own,
+        _ => false,
+    }
+}
+
+// Worker popover (kontrol baterai, picker WiFi) membalas setiap perintah dengan
+// state terbaru. State mengisi ulang label status popover, jadi error perintah
+// dikirim sesudahnya supaya tidak langsung tertimpa.
+// False kalau popover sudah tidak ada (receiver di-drop), worker boleh berhenti.
+pub fn send_state<E>(sender: &mpsc::Sender<E>, state: E, error: Option<String>, to_event: impl FnOnce(String) -> E) -> bool {
+    if sender.send(state).is_err() {
+        return false;
+    }
+    match error {
+        Some(message) => sender.send(to_event(message)).is_ok(),
+        None => true,
+    }
+}
@@ -0,0 +1,186 @@
+// Utilitas teks bersama untuk semua modul yang memendekkan teks.
+// Semua perhitungan pakai grapheme (bukan byte), jadi aman untuk
+// judul Jepang, emoji, atau nama artis beraksen.
+use gtk4::prelude::*;
+use gtk4::Label;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+
+const ELLIPSIS: &str = "...";
+const MARQUEE_GAP: &str = "   ";
+
+// Potong teks jadi maksimal `max` grapheme (termasuk "...")
+pub fn truncate(text: &str, max: usize) -> String {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if graphemes.len() <= max {
+        return text.to_string();
+    }
+
+    // Batas terlalu sempit untuk "...": potong saja, jangan melebihi `max`
+    if max <= ELLIPSIS.len() {
+        return graphemes[..max].concat();
+    }
+
+    let keep = max - ELLIPSIS.len();
+    format!("{}{}", graphemes[..keep].concat(), ELLIPSIS)
+}
+
+struct MarqueeState {
+    prefix: String,
+    text: String,
+    graphemes: Vec<String>, // Teks + jarak pemisah, diputar terus
+    offset: usize,
+    timer: Option<glib::SourceId>,
+}
+
+// Teks berjalan untuk judul panjang. Timer hanya aktif kalau teksnya
+// memang lebih panjang dari `width`, jadi teks pendek tidak bikin wakeup.
+pub struct Marquee {
+    label: Label,
+    width: usize,
+    interval: Duration,
+    state: Rc<RefCell<MarqueeState>>,
+}
+
+impl Marquee {
+    pub fn new(label: &Label, width: usize, interval: Duration) -> Self {
+        Self {
+            label: label.clone(),
+            width,
+            interval,
+            state: Rc::new(RefCell::new(MarqueeState {
+                prefix: String::new(),
+                text: String::new(),
+                graphemes: Vec::new(),
+                offset: 0,
+                timer: None,
+            })),
+        }
+    }
+
+    // `prefix` (misal ikon) selalu diam di depan, hanya `text` yang berjalan
+    pub fn set_text(&self, prefix: &str, text: &str) {
+        let mut state = self.state.borrow_mut();
+
+        let graphemes: Vec<String> = text.graphemes(true).map(|g| g.to_string()).collect();
+        let needs_scroll = graphemes.len() > self.width;
+
+        // Judul sama -> jangan reset posisi scroll (cukup update prefix)
+        let same_text = state.text == text;
+        state.prefix = prefix.to_string();
+        state.text = text.to_string();
+
+        if !needs_scroll {
+            if let Some(source_id) = state.timer.take() {
+                source_id.remove();
+            }
+            state.graphemes = graphemes;
+            state.offset = 0;
+            self.label.set_text(&format!("{}{}", prefix, text));
+            return;
+        }
+
+        if !same_text || state.timer.is_none() {
+            state.graphemes = graphemes;
+            state.graphemes.extend(MARQUEE_GAP.graphemes(true).map(|g| g.to_string()));
+            state.offset = 0;
+        }
+        Self::render(&self.label, &state, self.width);
+
+        if state.timer.is_none() {
+            let label_weak = self.label.downgrade();
+            let state_rc = self.state.clone();
+            let width = self.width;
+
+            let source_id = glib::timeout_add_local(self.interval, move || {
+                let label = match label_weak.upgrade() {
+                    Some(l) => l,
+                    None => return glib::ControlFlow::Break,
+                };
+                let mut state = state_rc.borrow_mut();
+                if state.graphemes.is_empty() {
+                    return glib::ControlFlow::Continue;
+                }
+                state.offset = (state.offset + 1) % state.graphemes.len();
+                Self::render(&label, &state, width);
+                glib::ControlFlow::Continue
+            });
+            state.timer = Some(source_id);
+        }
+    }
+
+    // Hentikan animasi (misal widget disembunyikan); set_text berikutnya memulai lagi
+    pub fn stop(&self) {
+        if let Some(source_id) = self.state.borrow_mut().timer.take() {
+            source_id.remove();
+        }
+    }
+
+    fn render(label: &Label, state: &MarqueeState, width: usize) {
+        let visible: String = state.graphemes
+            .iter()
+            .cycle()
+            .skip(state.offset)
+            .take(width)
+            .map(|g| g.as_str())
+            .collect();
+        label.set_text(&format!("{}{}", state.prefix, visible));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    #[test]
+    fn short_text_is_unchanged() {
+        assert_eq!(truncate("Halo", 10), "Halo");
+        assert_eq!(truncate("Halo", 4), "Halo");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn long_text_gets_ellipsis_within_limit() {
+        assert_eq!(truncate("Bohemian Rhapsody", 10), "Bohemia...");
+        assert_eq!(truncate("Bohemian Rhapsody", 10).graphemes(true).count(), 10);
+    }
+
+    #[test]
+    fn tiny_limit_never_exceeds_max() {
+        assert_eq!(truncate("Bohemian", 0), "");
+        assert_eq!(truncate("Bohemian", 1), "B");
+        assert_eq!(truncate("Bohemian", 2), "Bo");
+        assert_eq!(truncate("Bohemian", 3), "Boh");
+        assert_eq!(truncate("Bohemian", 4), "B...");
+    }
+
+    #[test]
+    fn cjk_counts_characters_not_bytes() {
+        assert_eq!(truncate("夜に駆ける", 5), "夜に駆ける");
+        assert_eq!(truncate("夜に駆ける YOASOBI", 6), "夜に駆...");
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_base() {
+        // "e" + U+0301 (aksen) adalah satu grapheme
+        let text = "Beyonce\u{301} Knowles";
+        assert_eq!(truncate(text, 10), "Beyonce\u{301}...");
+        assert_eq!(truncate(text, 3), "Bey");
+        let cut = truncate("e\u{301}e\u{301}e\u{301}e\u{301}", 2);
+        assert_eq!(cut, "e\u{301}e\u{301}");
+    }
+
+    #[test]
+    fn emoji_zwj_sequences_are_not_split() {
+        // Keluarga (👨‍👩‍👧) dan bendera pelangi (🏳️‍🌈) masing-masing satu grapheme
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let rainbow = "\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}";
+        let text = format!("{}{}{}{}{}", family, rainbow, family, rainbow, family);
+        assert_eq!(truncate(&text, 5), text);
+        assert_eq!(truncate(&text, 4), format!("{}...", family));
+        assert_eq!(truncate(&text, 2), format!("{}{}", family, rainbow));
+    }
+}